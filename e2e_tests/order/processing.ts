@@ -71,7 +71,7 @@ async function run(_nodeName: string, networkInfo: any, _jsArgs: any) {
   };
 
   log('Creating order');
-  const createOrderCall = regionXApi.tx.orders.createOrder(paraId, orderRequirements);
+  const createOrderCall = regionXApi.tx.orders.createOrder(paraId, orderRequirements, null);
   await submitExtrinsic(alice, createOrderCall, {});
 
   const order = (await regionXApi.query.orders.orders(0)).toJSON();
@@ -79,6 +79,8 @@ async function run(_nodeName: string, networkInfo: any, _jsArgs: any) {
     creator: alice.address,
     paraId: 2000,
     requirements: orderRequirements,
+    maxReward: null,
+    fundingTarget: null,
   });
 
   log('Giving Bob tokens');
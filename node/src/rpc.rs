@@ -22,7 +22,10 @@
 
 use std::sync::Arc;
 
-use regionx_runtime_common::primitives::{opaque::Block, AccountId, Balance, Nonce};
+use regionx_runtime_common::{
+	assets::AssetId,
+	primitives::{opaque::Block, AccountId, Balance, Nonce},
+};
 
 use pallet_ismp_rpc::{IsmpApiServer, IsmpRpcHandler};
 use sc_client_api::{AuxStore, BlockBackend, ProofProvider};
@@ -66,10 +69,12 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>,
+	C::Api: pallet_market_rpc::MarketRuntimeApi<Block, AccountId, Balance, AssetId>,
 	P: TransactionPool + Sync + Send + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashingFor<Block>>,
 {
+	use pallet_market_rpc::{Market, MarketApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -78,6 +83,7 @@ where
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Market::<_, _, Balance>::new(client.clone()).into_rpc())?;
 	module.merge(IsmpRpcHandler::new(client, backend.clone())?.into_rpc())?;
 
 	Ok(module)
@@ -15,7 +15,10 @@
 
 use cumulus_primitives_core::CollectCollationInfo;
 use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
-use regionx_runtime_common::primitives::{AccountId, AuraId, Balance, Block, Nonce};
+use regionx_runtime_common::{
+	assets::AssetId,
+	primitives::{AccountId, AuraId, Balance, Block, Nonce},
+};
 use sc_offchain::OffchainWorkerApi;
 use sp_api::{ApiExt, Metadata};
 use sp_block_builder::BlockBuilder;
@@ -39,6 +42,7 @@ pub trait BaseHostRuntimeApis:
 	+ ismp_parachain_runtime_api::IsmpParachainApi<Block>
 	+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
 	+ pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>
+	+ pallet_market_runtime_api::MarketApi<Block, AccountId, Balance, AssetId>
 {
 }
 
@@ -56,5 +60,6 @@ impl<Api> BaseHostRuntimeApis for Api where
 		+ ismp_parachain_runtime_api::IsmpParachainApi<Block>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
 		+ pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>
+		+ pallet_market_runtime_api::MarketApi<Block, AccountId, Balance, AssetId>
 {
 }
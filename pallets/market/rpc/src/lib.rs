@@ -0,0 +1,242 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the Coretime marketplace.
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_broker::{RegionId, Timeslice};
+use pallet_market::Listing;
+pub use pallet_market_runtime_api::MarketApi as MarketRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// The price for which a listed region can be purchased.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Quote<AssetId> {
+	/// The asset in which the price has to be paid.
+	pub asset: AssetId,
+	/// The total price of the region.
+	pub price: NumberOrHex,
+}
+
+/// A region listed on sale.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingInfo<AccountId, AssetId> {
+	/// The `u128` encoded region id.
+	pub region_id: NumberOrHex,
+	/// The `AccountId` selling the region.
+	pub seller: AccountId,
+	/// The price per a single timeslice.
+	pub timeslice_price: NumberOrHex,
+	/// The asset in which the region is priced.
+	pub asset: AssetId,
+	/// The accounts receiving the payment from the sale along with their share of it, in parts
+	/// per million.
+	pub sale_recipients: Vec<(AccountId, u32)>,
+	/// The timeslice at which the listing expires.
+	pub expires_at: Option<Timeslice>,
+	/// The deposit taken from the seller.
+	pub deposit: NumberOrHex,
+	/// The accounts allowed to purchase the region, if the listing is private.
+	pub allowed_buyers: Option<Vec<AccountId>>,
+	/// The minimum number of timeslices the region has to remain usable for to be sold.
+	pub min_remaining_timeslices: Timeslice,
+}
+
+/// A page of active listings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingsPage<AccountId, AssetId> {
+	/// The listings on this page.
+	pub listings: Vec<ListingInfo<AccountId, AssetId>>,
+	/// The `u128` encoded region id to pass as `start_after` for the next page, or `None` if
+	/// there are no more listings.
+	pub next: Option<NumberOrHex>,
+}
+
+impl<AccountId, Balance: Into<NumberOrHex>, AssetId>
+	From<(RegionId, Listing<AccountId, Balance, AssetId>)> for ListingInfo<AccountId, AssetId>
+{
+	fn from((region_id, listing): (RegionId, Listing<AccountId, Balance, AssetId>)) -> Self {
+		Self {
+			region_id: u128::from(region_id).into(),
+			seller: listing.seller,
+			timeslice_price: listing.timeslice_price.into(),
+			asset: listing.asset,
+			sale_recipients: listing
+				.sale_recipients
+				.into_iter()
+				.map(|(recipient, share)| (recipient, share.deconstruct()))
+				.collect(),
+			expires_at: listing.expires_at,
+			deposit: listing.deposit.into(),
+			allowed_buyers: listing.allowed_buyers.map(|buyers| buyers.into_inner()),
+			min_remaining_timeslices: listing.min_remaining_timeslices,
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait MarketApi<BlockHash, AccountId, AssetId> {
+	/// Returns the current price of a listed region.
+	///
+	/// `region_id` is the `u128` encoded region id.
+	#[method(name = "market_quote")]
+	fn quote(
+		&self,
+		region_id: NumberOrHex,
+		payment_asset: Option<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Quote<AssetId>>>;
+
+	/// Returns up to `limit` active listings, starting after the `start_after` region.
+	///
+	/// The page size is capped by the runtime.
+	#[method(name = "market_listings")]
+	fn listings(
+		&self,
+		start_after: Option<NumberOrHex>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<ListingsPage<AccountId, AssetId>>;
+
+	/// Returns all the listings of a seller.
+	#[method(name = "market_sellerListings")]
+	fn seller_listings(
+		&self,
+		seller: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ListingInfo<AccountId, AssetId>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The region id is invalid.
+	InvalidRegionId,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidRegionId => 2,
+		}
+	}
+}
+
+/// Provides RPC methods to query the Coretime marketplace.
+pub struct Market<C, Block, Balance> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Balance)>,
+}
+
+impl<C, Block, Balance> Market<C, Block, Balance> {
+	/// Creates a new instance of the Market Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the market.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+fn decode_region_id(region_id: NumberOrHex) -> RpcResult<RegionId> {
+	let region_id: u128 = region_id.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::InvalidRegionId.into(),
+			"Region id doesn't fit in u128.",
+			None::<()>,
+		))
+	})?;
+	Ok(region_id.into())
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance, AssetId>
+	MarketApiServer<<Block as BlockT>::Hash, AccountId, AssetId> for Market<C, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MarketRuntimeApi<Block, AccountId, Balance, AssetId>,
+	AccountId: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	Balance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+	AssetId: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+	fn quote(
+		&self,
+		region_id: NumberOrHex,
+		payment_asset: Option<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Quote<AssetId>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let quote = api
+			.quote(at_hash, decode_region_id(region_id)?, payment_asset)
+			.map_err(runtime_error)?;
+
+		Ok(quote.map(|(asset, price)| Quote { asset, price: price.into() }))
+	}
+
+	fn listings(
+		&self,
+		start_after: Option<NumberOrHex>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<ListingsPage<AccountId, AssetId>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let start_after = start_after.map(decode_region_id).transpose()?;
+		let (listings, next) = api.listings(at_hash, start_after, limit).map_err(runtime_error)?;
+
+		Ok(ListingsPage {
+			listings: listings.into_iter().map(Into::into).collect(),
+			next: next.map(|region_id| u128::from(region_id).into()),
+		})
+	}
+
+	fn seller_listings(
+		&self,
+		seller: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ListingInfo<AccountId, AssetId>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let listings = api.seller_listings(at_hash, seller).map_err(runtime_error)?;
+
+		Ok(listings.into_iter().map(Into::into).collect())
+	}
+}
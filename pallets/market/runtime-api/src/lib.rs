@@ -0,0 +1,63 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the Coretime marketplace.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_broker::RegionId;
+use pallet_market::{Listing, PricePoint};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// API for querying the listings of the Coretime marketplace.
+	pub trait MarketApi<AccountId, Balance, AssetId>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		AssetId: Codec,
+	{
+		/// Returns the asset and the price for which a listed region can currently be purchased.
+		///
+		/// If `payment_asset` is specified the price is converted the same way as when purchasing
+		/// the region. Returns `None` if the region cannot be purchased.
+		fn quote(region_id: RegionId, payment_asset: Option<AssetId>) -> Option<(AssetId, Balance)>;
+
+		/// Returns up to `limit` active listings, starting after the `start_after` region.
+		///
+		/// The page size is capped by the runtime. Along with the listings, the region to use as
+		/// `start_after` for the next page is returned, or `None` if there are no more listings.
+		fn listings(
+			start_after: Option<RegionId>,
+			limit: u32,
+		) -> (Vec<(RegionId, Listing<AccountId, Balance, AssetId>)>, Option<RegionId>);
+
+		/// Returns all the listings of a seller.
+		fn seller_listings(seller: AccountId) -> Vec<(RegionId, Listing<AccountId, Balance, AssetId>)>;
+
+		/// Returns the most recent purchases.
+		///
+		/// Prices are denominated in the native currency and normalised to the price of a single
+		/// timeslice of a full core.
+		fn price_history() -> Vec<PricePoint<Balance>>;
+
+		/// Returns the normalised price of the most recent purchase.
+		fn latest_price() -> Option<Balance>;
+
+		/// Returns the normalised time-weighted average price of the given bulk period.
+		fn average_price(period: u32) -> Option<Balance>;
+	}
+}
@@ -20,12 +20,35 @@
 use super::*;
 
 use frame_benchmarking::v2::*;
-use frame_support::traits::fungible::Mutate;
+use frame_support::{
+	traits::{fungible::Mutate, EnsureOrigin},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
 use pallet_broker::{CoreMask, RegionId, RegionRecord};
+use sp_core::bounded_vec;
 
 const SEED: u32 = 0;
 
+/// Splits the sale revenue evenly among the maximum number of recipients.
+fn sale_recipients<T: Config>() -> Result<SaleRecipients<T::AccountId>, BenchmarkError> {
+	let share = Permill::one().deconstruct() / MAX_SALE_RECIPIENTS;
+	let mut recipients = Vec::new();
+	for i in 0..MAX_SALE_RECIPIENTS {
+		let recipient: T::AccountId = account("recipient", i, SEED);
+		// Make sure the recipients exist, regardless of the existential deposit:
+		T::Assets::deposit(T::NativeAssetId::get(), &recipient, u32::MAX.into())?;
+		let share = if i == MAX_SALE_RECIPIENTS - 1 {
+			Permill::one().deconstruct() - share * (MAX_SALE_RECIPIENTS - 1)
+		} else {
+			share
+		};
+		recipients.push((recipient, Permill::from_parts(share)));
+	}
+
+	Ok(recipients.try_into().expect("Within bounds"))
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
@@ -40,18 +63,31 @@ mod benchmarks {
 
 		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
 		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: caller.clone(), paid: None };
+		<T as crate::Config>::Currency::set_balance(&caller.clone(), u32::MAX.into());
 		T::Regions::create_region(region_id, record, caller.clone())?;
 
 		let timeslice_price: BalanceOf<T> = 1_000u32.into();
+		let allowed_buyers: AllowedBuyers<T::AccountId> = (0..MAX_ALLOWED_BUYERS)
+			.map(|i| account("buyer", i, SEED))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("Within bounds");
 		#[extrinsic_call]
-		_(RawOrigin::Signed(caller.clone()), region_id, timeslice_price, None);
+		_(
+			RawOrigin::Signed(caller.clone()),
+			region_id,
+			timeslice_price,
+			T::NativeAssetId::get(),
+			ListingOptions { allowed_buyers: Some(allowed_buyers), ..Default::default() },
+		);
 
 		assert_last_event::<T>(
 			Event::Listed {
 				region_id,
 				timeslice_price,
+				asset: T::NativeAssetId::get(),
 				seller: caller.clone(),
-				sale_recipient: caller,
+				sale_recipients: bounded_vec![(caller, Permill::one())],
 			}
 			.into(),
 		);
@@ -62,23 +98,36 @@ mod benchmarks {
 	#[benchmark]
 	fn unlist_region() -> Result<(), BenchmarkError> {
 		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
 
 		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
-		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: caller.clone(), paid: None };
-		T::Regions::create_region(region_id, record, caller.clone())?;
+		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: alice.clone(), paid: None };
+		<T as crate::Config>::Currency::set_balance(&alice.clone(), u32::MAX.into());
+		<T as crate::Config>::Currency::set_balance(&caller.clone(), u32::MAX.into());
+		<T as crate::Config>::Currency::set_balance(&T::TreasuryAccount::get(), u32::MAX.into());
+		T::Regions::create_region(region_id, record, alice.clone())?;
 
 		let timeslice_price: BalanceOf<T> = 1_000u32.into();
 		crate::Pallet::<T>::list_region(
-			RawOrigin::Signed(caller.clone()).into(),
+			RawOrigin::Signed(alice).into(),
 			region_id,
 			timeslice_price,
-			None,
+			T::NativeAssetId::get(),
+			Default::default(),
 		)?;
+		// The worst case is someone else removing an expired listing.
+		Listings::<T>::mutate(region_id, |listing| {
+			if let Some(listing) = listing {
+				listing.expires_at = Some(0);
+			}
+		});
 
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller.clone()), region_id);
 
-		assert_last_event::<T>(Event::Unlisted { region_id }.into());
+		assert_last_event::<T>(
+			Event::Unlisted { region_id, who: Some(caller), reason: UnlistReason::Expired }.into(),
+		);
 
 		Ok(())
 	}
@@ -89,13 +138,15 @@ mod benchmarks {
 
 		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
 		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: caller.clone(), paid: None };
+		<T as crate::Config>::Currency::set_balance(&caller.clone(), u32::MAX.into());
 		T::Regions::create_region(region_id, record, caller.clone())?;
 
 		crate::Pallet::<T>::list_region(
 			RawOrigin::Signed(caller.clone()).into(),
 			region_id,
 			1_000u32.into(),
-			None,
+			T::NativeAssetId::get(),
+			Default::default(),
 		)?;
 
 		let new_timeslice_price = 2_000u32.into();
@@ -117,25 +168,372 @@ mod benchmarks {
 
 		<T as crate::Config>::Currency::set_balance(&alice.clone(), u32::MAX.into());
 		T::Regions::create_region(region_id, record, alice.clone())?;
+		let recipients = sale_recipients::<T>()?;
 		crate::Pallet::<T>::list_region(
-			RawOrigin::Signed(alice).into(),
+			RawOrigin::Signed(alice.clone()).into(),
 			region_id,
 			1_000u32.into(),
-			None,
+			T::NativeAssetId::get(),
+			ListingOptions { sale_recipients: Some(recipients.clone()), ..Default::default() },
 		)?;
 
-		<T as crate::Config>::Currency::set_balance(&caller.clone(), u32::MAX.into());
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
 		let max_price = 8000u32.into();
+		// The price is split evenly among the recipients.
+		let payments: SalePayments<T::AccountId, BalanceOf<T>> = recipients
+			.into_iter()
+			.map(|(recipient, _)| (recipient, 1_000u32.into()))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("Within bounds");
 
 		#[extrinsic_call]
-		_(RawOrigin::Signed(caller.clone()), region_id, max_price);
+		_(RawOrigin::Signed(caller.clone()), region_id, max_price, None);
 
 		assert_last_event::<T>(
-			Event::Purchased { region_id, total_price: max_price, buyer: caller }.into(),
+			Event::Purchased {
+				region_id,
+				buyer: caller.clone(),
+				seller: alice,
+				sale_recipients: payments,
+				asset: T::NativeAssetId::get(),
+				total_price: max_price,
+				timeslice_price: 1_000u32.into(),
+				remaining_duration: 8,
+				fee: 0u32.into(),
+			}
+			.into(),
 		);
 
 		Ok(())
 	}
 
+	#[benchmark]
+	fn purchase_many(n: Linear<1, { T::MaxBasketSize::get() }>) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let mut purchases = BoundedVec::new();
+		for i in 0..n {
+			// Each region is listed by a different seller so that the listing limit is not hit.
+			let seller: T::AccountId = account("seller", i, SEED);
+			let region_id = RegionId { begin: 0, core: i as u16, mask: CoreMask::complete() };
+			let record: RegionRecordOf<T> =
+				RegionRecord { end: 8, owner: seller.clone(), paid: None };
+
+			<T as crate::Config>::Currency::set_balance(&seller.clone(), u32::MAX.into());
+			T::Regions::create_region(region_id, record, seller.clone())?;
+			crate::Pallet::<T>::list_region(
+				RawOrigin::Signed(seller).into(),
+				region_id,
+				1_000u32.into(),
+				T::NativeAssetId::get(),
+				ListingOptions {
+					sale_recipients: Some(sale_recipients::<T>()?),
+					..Default::default()
+				},
+			)?;
+
+			purchases
+				.try_push((region_id, 8000u32.into()))
+				.map_err(|_| BenchmarkError::Weightless)?;
+		}
+
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+		let total_max = (8000u32 * n).into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), purchases, total_max, None);
+
+		assert_eq!(Listings::<T>::iter().count(), 0);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn propose_swap() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		T::Regions::create_region(
+			offered,
+			RegionRecord { end: 8, owner: caller.clone(), paid: None },
+			caller.clone(),
+		)?;
+		T::Regions::create_region(
+			requested,
+			RegionRecord { end: 8, owner: alice.clone(), paid: None },
+			alice,
+		)?;
+
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+		let top_up = Some((T::NativeAssetId::get(), 1_000u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), offered, requested, top_up, 8);
+
+		assert_last_event::<T>(
+			Event::SwapProposed { offered, requested, proposer: caller, top_up, expires_at: 8 }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_swap() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		T::Regions::create_region(
+			offered,
+			RegionRecord { end: 8, owner: alice.clone(), paid: None },
+			alice.clone(),
+		)?;
+		T::Regions::create_region(
+			requested,
+			RegionRecord { end: 8, owner: caller.clone(), paid: None },
+			caller.clone(),
+		)?;
+
+		T::Assets::deposit(T::NativeAssetId::get(), &alice, u32::MAX.into())?;
+		crate::Pallet::<T>::propose_swap(
+			RawOrigin::Signed(alice.clone()).into(),
+			offered,
+			requested,
+			Some((T::NativeAssetId::get(), 1_000u32.into())),
+			8,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), offered);
+
+		assert_last_event::<T>(
+			Event::SwapAccepted { offered, requested, proposer: alice, counterparty: caller }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_swap() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		T::Regions::create_region(
+			offered,
+			RegionRecord { end: 8, owner: caller.clone(), paid: None },
+			caller.clone(),
+		)?;
+		T::Regions::create_region(
+			requested,
+			RegionRecord { end: 8, owner: alice.clone(), paid: None },
+			alice,
+		)?;
+
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+		crate::Pallet::<T>::propose_swap(
+			RawOrigin::Signed(caller.clone()).into(),
+			offered,
+			requested,
+			Some((T::NativeAssetId::get(), 1_000u32.into())),
+			8,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), offered);
+
+		assert_last_event::<T>(Event::SwapCancelled { offered }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_buy_order() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+
+		let max_timeslice_price: BalanceOf<T> = 1_000u32.into();
+		let budget: BalanceOf<T> = 8_000u32.into();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			T::NativeAssetId::get(),
+			max_timeslice_price,
+			57600, // Full core.
+			0,
+			8,
+			budget,
+		);
+
+		assert_last_event::<T>(
+			Event::BuyOrderCreated {
+				order_id: 0,
+				buyer: caller,
+				asset: T::NativeAssetId::get(),
+				max_timeslice_price,
+				min_occupancy: 57600,
+				begin: 0,
+				end: 8,
+				budget,
+			}
+			.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_buy_order() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+
+		crate::Pallet::<T>::create_buy_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::NativeAssetId::get(),
+			1_000u32.into(),
+			57600, // Full core.
+			0,
+			8,
+			8_000u32.into(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert_last_event::<T>(Event::BuyOrderCancelled { order_id: 0 }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn match_buy_orders(n: Linear<1, { T::MaxBuyOrders::get() }>) -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: alice.clone(), paid: None };
+		<T as crate::Config>::Currency::set_balance(&alice.clone(), u32::MAX.into());
+		T::Regions::create_region(region_id, record, alice.clone())?;
+		crate::Pallet::<T>::list_region(
+			RawOrigin::Signed(alice).into(),
+			region_id,
+			1_000u32.into(),
+			T::NativeAssetId::get(),
+			ListingOptions { sale_recipients: Some(sale_recipients::<T>()?), ..Default::default() },
+		)?;
+
+		// Only the last buy order matches the listing.
+		for i in 0..n {
+			let buyer: T::AccountId = account("buyer", i, SEED);
+			T::Assets::deposit(T::NativeAssetId::get(), &buyer, u32::MAX.into())?;
+			let max_timeslice_price = if i == n - 1 { 1_000u32 } else { 999u32 };
+			crate::Pallet::<T>::create_buy_order(
+				RawOrigin::Signed(buyer).into(),
+				T::NativeAssetId::get(),
+				max_timeslice_price.into(),
+				57600, // Full core.
+				0,
+				8,
+				8_000u32.into(),
+			)?;
+		}
+
+		#[block]
+		{
+			crate::Pallet::<T>::match_buy_orders(region_id);
+		}
+
+		assert!(Listings::<T>::get(region_id).is_none());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sweep(n: Linear<1, { T::MaxSweepCandidates::get() }>) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		// All listed regions match the requirements, the last one is the cheapest.
+		for i in 0..n {
+			let seller: T::AccountId = account("seller", i, SEED);
+			let region_id = RegionId { begin: 0, core: i as u16, mask: CoreMask::complete() };
+			let record: RegionRecordOf<T> =
+				RegionRecord { end: 8, owner: seller.clone(), paid: None };
+
+			<T as crate::Config>::Currency::set_balance(&seller.clone(), u32::MAX.into());
+			T::Regions::create_region(region_id, record, seller.clone())?;
+			crate::Pallet::<T>::list_region(
+				RawOrigin::Signed(seller).into(),
+				region_id,
+				(2_000u32 - i).into(),
+				T::NativeAssetId::get(),
+				ListingOptions {
+					sale_recipients: Some(sale_recipients::<T>()?),
+					..Default::default()
+				},
+			)?;
+		}
+
+		T::Assets::deposit(T::NativeAssetId::get(), &caller, u32::MAX.into())?;
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 57600 };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), requirements, 16_000u32.into());
+
+		let cheapest = RegionId { begin: 0, core: (n - 1) as u16, mask: CoreMask::complete() };
+		assert!(Listings::<T>::get(cheapest).is_none());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_market_fee() -> Result<(), BenchmarkError> {
+		let origin =
+			T::MarketFeeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let fee = Permill::from_percent(5);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, fee);
+
+		assert_last_event::<T>(Event::MarketFeeUpdated { fee }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_expired_listing() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: caller.clone(), paid: None };
+		<T as crate::Config>::Currency::set_balance(&caller.clone(), u32::MAX.into());
+		T::Regions::create_region(region_id, record, caller.clone())?;
+
+		crate::Pallet::<T>::list_region(
+			RawOrigin::Signed(caller.clone()).into(),
+			region_id,
+			1_000u32.into(),
+			T::NativeAssetId::get(),
+			Default::default(),
+		)?;
+		let listing = Listings::<T>::get(region_id).ok_or(BenchmarkError::Weightless)?;
+
+		#[block]
+		{
+			crate::Pallet::<T>::release_deposit(&listing)?;
+			crate::Pallet::<T>::remove_listing(region_id, &listing)?;
+		}
+
+		assert!(Listings::<T>::get(region_id).is_none());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
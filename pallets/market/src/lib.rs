@@ -15,12 +15,25 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::{fungible::Inspect, tokens::Preservation};
+use frame_support::{
+	pallet_prelude::DispatchError,
+	traits::{
+		fungible::{Inspect, MutateHold},
+		tokens::{ConversionFromAssetBalance, Fortitude, Precision, Restriction},
+		Contains, Get,
+	},
+};
 use nonfungible_primitives::LockableNonFungible;
+use order_primitives::{core_occupancy, Requirements};
+use orml_traits::MultiCurrency;
 pub use pallet::*;
-use pallet_broker::{RegionId, Timeslice};
-use region_primitives::{RegionFactory, RegionInspect};
-use sp_runtime::{traits::BlockNumberProvider, SaturatedConversion, Saturating};
+use pallet_broker::{PartsOf57600, RegionId, Timeslice};
+use region_primitives::{CoretimePriceOracle, RegionFactory, RegionInspect, RegionMarket};
+use scale_info::prelude::vec::Vec;
+use sp_runtime::{
+	traits::{AccountIdConversion, BlockNumberProvider, Zero},
+	Permill, SaturatedConversion, Saturating,
+};
 
 mod types;
 pub use crate::types::*;
@@ -31,12 +44,25 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+const LOG_TARGET: &str = "runtime::market";
+
+/// The number of parts a core is split into by the `CoreMask`.
+const CORE_MASK_BITS: u32 = 80;
+
+/// The maximum number of listings returned by a single `active_listings` query.
+pub const MAX_LISTINGS_PAGE: u32 = 100;
+
+/// The maximum number of listings `active_listings` goes through in a single query, including
+/// the expired ones which are skipped.
+pub const MAX_SCANNED_LISTINGS: u32 = 1_000;
+
 pub type BalanceOf<T> =
 	<<T as crate::Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -49,9 +75,13 @@ pub mod pallet {
 	use super::*;
 	use frame_support::{
 		pallet_prelude::*,
+		storage::{with_storage_layer, StoragePrefixedMap},
 		traits::{fungible::Mutate, nonfungible::Transfer},
+		weights::WeightMeter,
+		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
+	use sp_core::bounded_vec;
 
 	/// The module configuration trait.
 	#[pallet::config]
@@ -59,8 +89,38 @@ pub mod pallet {
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		/// Currency used for purchasing coretime.
-		type Currency: Mutate<Self::AccountId>;
+		/// Currency used for the listing deposits.
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Identifier of the assets in which regions can be priced.
+		type AssetId: Parameter + Member + Copy + MaxEncodedLen;
+
+		/// Multi-currency implementation used for settling the purchases.
+		type Assets: MultiCurrency<
+			Self::AccountId,
+			CurrencyId = Self::AssetId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// The assets sellers can price their regions in.
+		type SupportedAssets: Contains<Self::AssetId>;
+
+		/// The asset id of the native currency.
+		#[pallet::constant]
+		type NativeAssetId: Get<Self::AssetId>;
+
+		/// Type for converting the price of a listing to the native currency.
+		///
+		/// This allows buyers to purchase regions priced in other assets with the native currency.
+		type AssetConverter: ConversionFromAssetBalance<
+			BalanceOf<Self>,
+			Self::AssetId,
+			BalanceOf<Self>,
+		>;
 
 		/// Type providing a way of reading, transferring and locking regions.
 		//
@@ -79,18 +139,167 @@ pub mod pallet {
 		#[pallet::constant]
 		type TimeslicePeriod: Get<RCBlockNumberOf<Self>>;
 
+		/// Type responsible for dealing with the market fees charged on purchases.
+		type FeeDestination: FeeHandler<Self::AccountId, Self::AssetId, BalanceOf<Self>>;
+
+		/// The origin which can update the market fee.
+		type MarketFeeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The market's pallet id, used for deriving the account holding the buy order budgets and
+		/// the swap top-ups.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The deposit a seller has to make when listing a region.
+		///
+		/// Returned once the region is unlisted or sold. If the listing expires and has to be
+		/// removed by someone else the deposit is slashed.
+		#[pallet::constant]
+		type ListingDeposit: Get<BalanceOf<Self>>;
+
+		/// The account receiving the slashed listing deposits.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The part of a slashed listing deposit that goes to the account removing the expired
+		/// listing.
+		#[pallet::constant]
+		type CleanupReward: Get<Permill>;
+
+		/// The maximum number of regions a single account can have listed at the same time.
+		#[pallet::constant]
+		type MaxListings: Get<u32>;
+
+		/// The maximum number of regions that can be purchased in a single `purchase_many` call.
+		#[pallet::constant]
+		type MaxBasketSize: Get<u32>;
+
+		/// The number of purchases kept in the price history.
+		#[pallet::constant]
+		type PriceHistoryLength: Get<u32>;
+
+		/// The maximum number of open buy orders.
+		///
+		/// Each new or repriced listing is checked against all open buy orders.
+		#[pallet::constant]
+		type MaxBuyOrders: Get<u32>;
+
+		/// The maximum number of buy orders that can be executed automatically in a single block.
+		#[pallet::constant]
+		type MaxAutoPurchases: Get<u32>;
+
+		/// The maximum number of listings considered by a single `sweep` call.
+		#[pallet::constant]
+		type MaxSweepCandidates: Get<u32>;
+
+		/// The length of a bulk period in timeslices.
+		///
+		/// The time-weighted average price is tracked separately for each bulk period.
+		#[pallet::constant]
+		type BulkPeriod: Get<Timeslice>;
+
 		/// Weight Info
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// A reason for the market placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are held as a deposit for a listed region.
+		ListingDeposit,
+	}
+
 	/// Regions that got listed on sale.
 	#[pallet::storage]
 	#[pallet::getter(fn listings)]
 	pub type Listings<T: Config> =
-		StorageMap<_, Blake2_128Concat, RegionId, Listing<T::AccountId, BalanceOf<T>>, OptionQuery>;
+		StorageMap<_, Blake2_128Concat, RegionId, ListingOf<T>, OptionQuery>;
+
+	/// Index of the listed regions, keyed by the end and begin timeslice of the region.
+	///
+	/// The keys are ordered by timeslice, which allows finding the regions lasting until a
+	/// certain timeslice without going through all the listings.
+	#[pallet::storage]
+	pub type ListingIndex<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Identity, OrderedTimeslice>,
+			NMapKey<Identity, OrderedTimeslice>,
+			NMapKey<Blake2_128Concat, RegionId>,
+		),
+		(),
+		OptionQuery,
+	>;
+
+	/// Regions listed by each seller.
+	#[pallet::storage]
+	#[pallet::getter(fn seller_listings)]
+	pub type SellerListings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<RegionId, T::MaxListings>,
+		ValueQuery,
+	>;
+
+	/// The last listing checked by the expired listing sweeper.
+	///
+	/// The sweeper continues from this listing in the next block. If `None` it starts from the
+	/// beginning.
+	#[pallet::storage]
+	pub type SweepCursor<T: Config> = StorageValue<_, RegionId, OptionQuery>;
+
+	/// Open swap proposals, indexed by the offered region.
+	#[pallet::storage]
+	#[pallet::getter(fn swaps)]
+	pub type Swaps<T: Config> = StorageMap<_, Blake2_128Concat, RegionId, SwapOf<T>, OptionQuery>;
+
+	/// The last swap proposal checked by the expired swap sweeper.
+	///
+	/// The sweeper continues from this proposal in the next block. If `None` it starts from the
+	/// beginning.
+	#[pallet::storage]
+	pub type SwapSweepCursor<T: Config> = StorageValue<_, RegionId, OptionQuery>;
+
+	/// Open buy orders waiting for a matching listing.
+	#[pallet::storage]
+	#[pallet::getter(fn buy_orders)]
+	pub type BuyOrders<T: Config> =
+		CountedStorageMap<_, Twox64Concat, BuyOrderId, BuyOrderOf<T>, OptionQuery>;
+
+	/// The index of the next buy order to be created.
+	#[pallet::storage]
+	pub type NextBuyOrderId<T: Config> = StorageValue<_, BuyOrderId, ValueQuery>;
+
+	/// The block in which buy orders were last executed and the number of orders executed in it.
+	#[pallet::storage]
+	pub type AutoPurchases<T: Config> = StorageValue<_, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	/// The most recent purchases, normalised to the price of a timeslice of a full core.
+	///
+	/// Once full, the oldest entry is dropped on each purchase.
+	#[pallet::storage]
+	#[pallet::getter(fn price_history)]
+	pub type PriceHistory<T: Config> =
+		StorageValue<_, BoundedVec<PricePoint<BalanceOf<T>>, T::PriceHistoryLength>, ValueQuery>;
+
+	/// Data for calculating the time-weighted average price of each bulk period.
+	#[pallet::storage]
+	#[pallet::getter(fn period_prices)]
+	pub type PeriodPrices<T: Config> =
+		StorageMap<_, Twox64Concat, u32, PeriodPrice<BalanceOf<T>>, OptionQuery>;
+
+	/// The fraction of the sale price which is charged as a fee on each purchase.
+	#[pallet::storage]
+	#[pallet::getter(fn market_fee)]
+	pub type MarketFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -101,22 +310,50 @@ pub mod pallet {
 			region_id: RegionId,
 			/// The price per timeslice of the listed region.
 			timeslice_price: BalanceOf<T>,
+			/// The asset in which the region is priced.
+			asset: T::AssetId,
 			/// The seller of the region.
 			seller: T::AccountId,
-			/// The sale revenue recipient.
-			sale_recipient: T::AccountId,
+			/// The sale revenue recipients along with their share of the revenue.
+			sale_recipients: SaleRecipients<T::AccountId>,
 		},
 		Unlisted {
 			/// The region that got unlisted.
 			region_id: RegionId,
+			/// The account removing the listing. `None` if it got removed automatically.
+			who: Option<T::AccountId>,
+			/// The reason the region got unlisted.
+			reason: UnlistReason,
 		},
 		Purchased {
 			/// The region that got purchased.
 			region_id: RegionId,
 			/// The buyer of the region.
 			buyer: T::AccountId,
+			/// The seller of the region.
+			seller: T::AccountId,
+			/// The accounts the sale revenue got paid to along with the amounts they received.
+			sale_recipients: SalePayments<T::AccountId, BalanceOf<T>>,
+			/// The asset used for paying.
+			asset: T::AssetId,
 			/// The total price paid for the listed region.
 			total_price: BalanceOf<T>,
+			/// The price per timeslice the region was listed for.
+			timeslice_price: BalanceOf<T>,
+			/// The number of timeslices for which the region is still usable.
+			remaining_duration: Timeslice,
+			/// The part of the total price that was charged as a market fee.
+			fee: BalanceOf<T>,
+		},
+		RegionSoldPartially {
+			/// The region that got purchased.
+			region_id: RegionId,
+			/// One of the accounts the sale revenue got split among.
+			sale_recipient: T::AccountId,
+			/// The share of the revenue the recipient is entitled to.
+			share: Permill,
+			/// The amount paid to the recipient.
+			amount: BalanceOf<T>,
 		},
 		PriceUpdated {
 			/// The region for which the sale price was updated.
@@ -124,6 +361,76 @@ pub mod pallet {
 			/// New timeslice price
 			new_timeslice_price: BalanceOf<T>,
 		},
+		MarketFeeUpdated {
+			/// The new market fee.
+			fee: Permill,
+		},
+		SwapProposed {
+			/// The region offered by the proposer.
+			offered: RegionId,
+			/// The region requested in return.
+			requested: RegionId,
+			/// The account proposing the swap.
+			proposer: T::AccountId,
+			/// The payment added on top of the offered region.
+			top_up: Option<(T::AssetId, BalanceOf<T>)>,
+			/// The timeslice at which the proposal expires.
+			expires_at: Timeslice,
+		},
+		SwapAccepted {
+			/// The region offered by the proposer.
+			offered: RegionId,
+			/// The region requested in return.
+			requested: RegionId,
+			/// The account that proposed the swap.
+			proposer: T::AccountId,
+			/// The account that accepted the swap.
+			counterparty: T::AccountId,
+		},
+		SwapCancelled {
+			/// The region that was offered in the cancelled swap.
+			offered: RegionId,
+		},
+		ListingDepositSlashed {
+			/// The region of the expired listing.
+			region_id: RegionId,
+			/// The seller whose deposit got slashed.
+			seller: T::AccountId,
+			/// The part of the deposit that went to the treasury.
+			amount: BalanceOf<T>,
+			/// The part of the deposit that went to the account removing the listing.
+			reward: BalanceOf<T>,
+		},
+		BuyOrderCreated {
+			/// The index of the created buy order.
+			order_id: BuyOrderId,
+			/// The account that placed the order.
+			buyer: T::AccountId,
+			/// The asset in which the region has to be priced.
+			asset: T::AssetId,
+			/// The maximum price per timeslice.
+			max_timeslice_price: BalanceOf<T>,
+			/// The minimum fraction of the core the region has to occupy.
+			min_occupancy: PartsOf57600,
+			/// The earliest timeslice at which the region can begin.
+			begin: Timeslice,
+			/// The latest timeslice at which the region can end.
+			end: Timeslice,
+			/// The funds escrowed for the purchase.
+			budget: BalanceOf<T>,
+		},
+		BuyOrderExecuted {
+			/// The index of the executed buy order.
+			order_id: BuyOrderId,
+			/// The region that got purchased.
+			region_id: RegionId,
+			/// The price paid for the region.
+			price: BalanceOf<T>,
+		},
+		BuyOrderCancelled {
+			/// The index of the cancelled buy order.
+			order_id: BuyOrderId,
+		},
 	}
 
 	#[pallet::error]
@@ -145,6 +452,50 @@ pub mod pallet {
 		RecordUnavailable,
 		/// Locked regions cannot be listed on sale.
 		RegionLocked,
+		/// The seller reached the maximum number of listings.
+		TooManyListings,
+		/// The listing expiry must be in the future.
+		InvalidExpiry,
+		/// The listing has expired.
+		ListingExpired,
+		/// Regions cannot be priced in the specified asset.
+		UnsupportedAsset,
+		/// The listing price cannot be paid in the specified asset.
+		UnsupportedConversion,
+		/// Failed to convert the listing price to the native currency.
+		ConversionFailed,
+		/// The regions of a basket purchase must all be paid in the same asset.
+		MixedAssets,
+		/// The basket purchase contains no regions.
+		EmptyBasket,
+		/// The swap proposal was not found.
+		UnknownSwap,
+		/// A region cannot be swapped for itself or for a region owned by the proposer.
+		InvalidSwap,
+		/// The swap proposal has expired.
+		SwapExpired,
+		/// The shares of the sale recipients don't add up to 100%.
+		InvalidSaleRecipients,
+		/// The maximum number of open buy orders has been reached.
+		TooManyBuyOrders,
+		/// The buy order was not found.
+		UnknownBuyOrder,
+		/// The buy order can never be matched.
+		InvalidBuyOrder,
+		/// No listed region matches the requirements within the specified price.
+		NoMatchingListing,
+		/// Fewer timeslices remain in the region than the seller's minimum.
+		ListingStale,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let consumed = Self::sweep_expired_listings(remaining_weight);
+			consumed.saturating_add(Self::sweep_expired_swaps(
+				remaining_weight.saturating_sub(consumed),
+			))
+		}
 	}
 
 	#[pallet::call]
@@ -154,19 +505,30 @@ pub mod pallet {
 		/// ## Arguments:
 		/// - `region_id`: The region that the caller intends to list for sale.
 		/// - `timeslice_price`: The price per a single timeslice.
-		/// - `sale_recipient`: The `AccountId` receiving the payment from the sale. If not
-		///   specified this will be the caller.
+		/// - `asset`: The asset in which the region is priced.
+		/// - `options`: The optional settings of the listing: the sale recipients, the expiry, the
+		///   allowed buyers and the minimum number of remaining timeslices. Once fewer timeslices
+		///   remain the listing goes stale. See `ListingOptions` for the defaults.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::list_region())]
+		#[pallet::weight(T::WeightInfo::list_region()
+			.saturating_add(T::WeightInfo::match_buy_orders(T::MaxBuyOrders::get())))]
 		pub fn list_region(
 			origin: OriginFor<T>,
 			region_id: RegionId,
 			timeslice_price: BalanceOf<T>,
-			sale_recipient: Option<T::AccountId>,
+			asset: T::AssetId,
+			options: ListingOptionsOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			let ListingOptions {
+				sale_recipients,
+				expires_at,
+				allowed_buyers,
+				min_remaining_timeslices,
+			} = options;
 
 			ensure!(Listings::<T>::get(region_id).is_none(), Error::<T>::AlreadyListed);
+			ensure!(T::SupportedAssets::contains(&asset), Error::<T>::UnsupportedAsset);
 
 			let region = T::Regions::region(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
 			ensure!(!region.locked, Error::<T>::RegionLocked);
@@ -175,26 +537,64 @@ pub mod pallet {
 			// It doesn't make sense to list a region that expired.
 			let current_timeslice = Self::current_timeslice();
 			ensure!(record.end > current_timeslice, Error::<T>::RegionExpired);
+			if let Some(expires_at) = expires_at {
+				ensure!(expires_at > current_timeslice, Error::<T>::InvalidExpiry);
+			}
+			ensure!(
+				Self::remaining_duration(region_id, &record) >= min_remaining_timeslices,
+				Error::<T>::ListingStale
+			);
+
+			let sale_recipients = match sale_recipients {
+				Some(recipients) => {
+					let total = recipients
+						.iter()
+						.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct()));
+					ensure!(
+						total == Permill::one().deconstruct(),
+						Error::<T>::InvalidSaleRecipients
+					);
+					recipients
+				},
+				None => bounded_vec![(who.clone(), Permill::one())],
+			};
+
+			SellerListings::<T>::try_mutate(&who, |listings| {
+				listings.try_push(region_id).map_err(|_| Error::<T>::TooManyListings)
+			})?;
 
 			T::Regions::lock(&region_id.into(), Some(who.clone()))?;
 
-			let sale_recipient = sale_recipient.unwrap_or(who.clone());
+			let deposit = T::ListingDeposit::get();
+			if !deposit.is_zero() {
+				T::Currency::hold(&HoldReason::ListingDeposit.into(), &who, deposit)?;
+			}
+
 			Listings::<T>::insert(
 				region_id,
 				Listing {
 					seller: who.clone(),
 					timeslice_price,
-					sale_recipient: sale_recipient.clone(),
+					asset,
+					sale_recipients: sale_recipients.clone(),
+					expires_at,
+					deposit,
+					allowed_buyers: allowed_buyers.filter(|buyers| !buyers.is_empty()),
+					min_remaining_timeslices,
 				},
 			);
+			ListingIndex::<T>::insert(Self::index_key(region_id, record.end), ());
 
 			Self::deposit_event(Event::Listed {
 				region_id,
 				timeslice_price,
+				asset,
 				seller: who,
-				sale_recipient,
+				sale_recipients,
 			});
 
+			Self::match_buy_orders(region_id);
+
 			Ok(())
 		}
 
@@ -210,15 +610,21 @@ pub mod pallet {
 			let listing = Listings::<T>::get(region_id).ok_or(Error::<T>::NotListed)?;
 			let record = T::Regions::record(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
 
-			// If the region expired anyone can remove it from the market.
-			let current_timeslice = Self::current_timeslice();
-			if current_timeslice <= record.end {
-				ensure!(who == listing.seller, Error::<T>::NotAllowed);
+			// If the region or the listing expired anyone can remove it from the market, at the
+			// cost of the seller's deposit.
+			let reason = if who == listing.seller {
+				Self::release_deposit(&listing)?;
+				UnlistReason::Seller
+			} else {
+				let reason =
+					Self::expiry_reason(region_id, &listing, &record, Self::current_timeslice())
+						.ok_or(Error::<T>::NotAllowed)?;
+				Self::slash_deposit(region_id, &listing, &who)?;
+				reason
 			};
 
-			Listings::<T>::remove(region_id);
-			T::Regions::unlock(&region_id.into(), None)?;
-			Self::deposit_event(Event::Unlisted { region_id });
+			Self::remove_listing(region_id, &listing)?;
+			Self::deposit_event(Event::Unlisted { region_id, who: Some(who), reason });
 
 			Ok(())
 		}
@@ -229,7 +635,8 @@ pub mod pallet {
 		/// - `region_id`: The region that is listed on sale.
 		/// - `new_timeslice_price`: The new timeslice price.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::update_region_price())]
+		#[pallet::weight(T::WeightInfo::update_region_price()
+			.saturating_add(T::WeightInfo::match_buy_orders(T::MaxBuyOrders::get())))]
 		pub fn update_region_price(
 			origin: OriginFor<T>,
 			region_id: RegionId,
@@ -250,6 +657,9 @@ pub mod pallet {
 			Listings::<T>::insert(region_id, listing);
 
 			Self::deposit_event(Event::PriceUpdated { region_id, new_timeslice_price });
+
+			Self::match_buy_orders(region_id);
+
 			Ok(())
 		}
 
@@ -260,31 +670,328 @@ pub mod pallet {
 		/// - `max_price`: The maximum price the buyer is willing to pay for the region. If the
 		///   actual price exceeds this amount, the purchase will not be executed. The region price
 		///   is linearly decreasing for currently active(i.e. usable) regions.
+		/// - `payment_asset`: The asset the buyer pays with. If not specified, or if it is the
+		///   asset of the listing, the price is paid in the listing asset. Otherwise only the
+		///   native currency is accepted, in which case the price gets converted and `max_price` is
+		///   denominated in the native currency. The sale recipient receives the payment in the
+		///   asset used by the buyer.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::purchase_region())]
 		pub fn purchase_region(
 			origin: OriginFor<T>,
 			region_id: RegionId,
 			max_price: BalanceOf<T>,
+			payment_asset: Option<T::AssetId>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::do_purchase(who, region_id, max_price, payment_asset)?;
 
-			let listing = Listings::<T>::get(region_id).ok_or(Error::<T>::NotListed)?;
-			let record = T::Regions::record(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
+			Ok(())
+		}
 
-			ensure!(who != listing.seller && who != listing.sale_recipient, Error::<T>::NotAllowed);
+		/// Extrinsic for updating the market fee.
+		///
+		/// ## Arguments:
+		/// - `fee`: The fraction of the sale price that will be charged on each purchase.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_market_fee())]
+		pub fn set_market_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
+			T::MarketFeeOrigin::ensure_origin(origin)?;
 
-			let price = Self::calculate_region_price(region_id, record, listing.timeslice_price);
-			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
-			T::Currency::transfer(&who, &listing.sale_recipient, price, Preservation::Preserve)?;
+			MarketFee::<T>::put(fee);
 
-			// Remove the region from sale:
-			Listings::<T>::remove(region_id);
-			T::Regions::unlock(&region_id.into(), None)?;
+			Self::deposit_event(Event::MarketFeeUpdated { fee });
+			Ok(())
+		}
 
-			T::Regions::transfer(&region_id.into(), &who)?;
+		/// Extrinsic for purchasing multiple regions listed on sale at once.
+		///
+		/// Either all of the regions get purchased or none of them.
+		///
+		/// ## Arguments:
+		/// - `purchases`: The regions to purchase along with the maximum price the buyer is willing
+		///   to pay for each of them.
+		/// - `total_max`: The maximum price the buyer is willing to pay for all the regions
+		///   combined.
+		/// - `payment_asset`: The asset the buyer pays with. See `purchase_region` for details. All
+		///   the regions must be paid in the same asset.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::purchase_many(purchases.len() as u32))]
+		pub fn purchase_many(
+			origin: OriginFor<T>,
+			purchases: BoundedVec<(RegionId, BalanceOf<T>), T::MaxBasketSize>,
+			total_max: BalanceOf<T>,
+			payment_asset: Option<T::AssetId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!purchases.is_empty(), Error::<T>::EmptyBasket);
+
+			// Check the whole basket before purchasing anything.
+			let mut basket_asset = None;
+			let mut total_price: BalanceOf<T> = Zero::zero();
+			for (region_id, max_price) in purchases.iter() {
+				let (_, asset, price) =
+					Self::quote_purchase(&who, *region_id, *max_price, payment_asset)?;
+
+				ensure!(basket_asset.map_or(true, |a| a == asset), Error::<T>::MixedAssets);
+				basket_asset = Some(asset);
+				total_price = total_price.saturating_add(price);
+			}
+			ensure!(total_price <= total_max, Error::<T>::PriceTooHigh);
+
+			for (region_id, max_price) in purchases {
+				Self::do_purchase(who.clone(), region_id, max_price, payment_asset)?;
+			}
+
+			Ok(())
+		}
+
+		/// Extrinsic for proposing to swap an owned region for a region owned by someone else.
+		///
+		/// Both regions are locked and the optional top-up is held by the market until the swap is
+		/// accepted, cancelled or expires. The owner of the requested region can release it at any
+		/// time by cancelling the proposal.
+		///
+		/// ## Arguments:
+		/// - `offered`: The region the caller is offering.
+		/// - `requested`: The region the caller wants to get in return.
+		/// - `top_up`: An optional payment the caller adds on top of the offered region.
+		/// - `expires_at`: The timeslice at which the proposal expires.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::propose_swap())]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			offered: RegionId,
+			requested: RegionId,
+			top_up: Option<(T::AssetId, BalanceOf<T>)>,
+			expires_at: Timeslice,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(offered != requested, Error::<T>::InvalidSwap);
+			ensure!(expires_at > Self::current_timeslice(), Error::<T>::InvalidExpiry);
+			if let Some((asset, _)) = top_up {
+				ensure!(T::SupportedAssets::contains(&asset), Error::<T>::UnsupportedAsset);
+			}
+
+			let record =
+				T::Regions::record(&offered.into()).ok_or(Error::<T>::RecordUnavailable)?;
+			ensure!(record.end > Self::current_timeslice(), Error::<T>::RegionExpired);
+
+			let requested_region =
+				T::Regions::region(&requested.into()).ok_or(Error::<T>::UnknownRegion)?;
+			ensure!(requested_region.owner != who, Error::<T>::InvalidSwap);
+
+			// Fails if the caller doesn't own the region or if either region is already locked.
+			T::Regions::lock(&offered.into(), Some(who.clone()))?;
+			T::Regions::lock(&requested.into(), None)?;
+
+			if let Some((asset, amount)) = top_up {
+				T::Assets::transfer(asset, &who, &Self::account_id(), amount)?;
+			}
+
+			Swaps::<T>::insert(
+				offered,
+				Swap { proposer: who.clone(), requested, top_up, expires_at },
+			);
+
+			Self::deposit_event(Event::SwapProposed {
+				offered,
+				requested,
+				proposer: who,
+				top_up,
+				expires_at,
+			});
+
+			Ok(())
+		}
+
+		/// Extrinsic for accepting a swap proposal.
+		///
+		/// The caller has to be the owner of the requested region. The regions are exchanged and
+		/// the caller receives the top-up in a single step.
+		///
+		/// ## Arguments:
+		/// - `offered`: The region offered in the swap proposal.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::accept_swap())]
+		pub fn accept_swap(origin: OriginFor<T>, offered: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let swap = Swaps::<T>::get(offered).ok_or(Error::<T>::UnknownSwap)?;
+			let current_timeslice = Self::current_timeslice();
+			ensure!(current_timeslice < swap.expires_at, Error::<T>::SwapExpired);
+
+			let requested =
+				T::Regions::region(&swap.requested.into()).ok_or(Error::<T>::UnknownRegion)?;
+			ensure!(requested.owner == who, Error::<T>::NotAllowed);
+			let record = requested.record.get().ok_or(Error::<T>::RecordUnavailable)?;
+			ensure!(record.end > current_timeslice, Error::<T>::RegionExpired);
+			let offered_record =
+				T::Regions::record(&offered.into()).ok_or(Error::<T>::RecordUnavailable)?;
+			ensure!(offered_record.end > current_timeslice, Error::<T>::RegionExpired);
+
+			Swaps::<T>::remove(offered);
+			T::Regions::unlock(&offered.into(), None)?;
+			T::Regions::unlock(&swap.requested.into(), None)?;
+
+			T::Regions::transfer(&offered.into(), &who)?;
+			T::Regions::transfer(&swap.requested.into(), &swap.proposer)?;
+			if let Some((asset, amount)) = swap.top_up {
+				T::Assets::transfer(asset, &Self::account_id(), &who, amount)?;
+			}
+
+			Self::deposit_event(Event::SwapAccepted {
+				offered,
+				requested: swap.requested,
+				proposer: swap.proposer,
+				counterparty: who,
+			});
+
+			Ok(())
+		}
+
+		/// Extrinsic for cancelling a swap proposal.
+		///
+		/// Both regions get unlocked and the top-up is returned to the proposer. Besides the
+		/// proposer, the owner of the requested region can reject the proposal this way.
+		///
+		/// ## Arguments:
+		/// - `offered`: The region offered in the swap proposal.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_swap())]
+		pub fn cancel_swap(origin: OriginFor<T>, offered: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let swap = Swaps::<T>::get(offered).ok_or(Error::<T>::UnknownSwap)?;
+
+			// If the proposal expired anyone can cancel it.
+			if !Self::is_swap_expired(offered, &swap, Self::current_timeslice()) {
+				let is_counterparty = T::Regions::region(&swap.requested.into())
+					.map_or(false, |region| region.owner == who);
+				ensure!(who == swap.proposer || is_counterparty, Error::<T>::NotAllowed);
+			}
+
+			Self::remove_swap(offered, &swap)?;
+			Self::deposit_event(Event::SwapCancelled { offered });
+
+			Ok(())
+		}
+
+		/// Extrinsic for placing a standing buy order.
+		///
+		/// The budget is escrowed by the market. Whenever a region matching the order gets listed,
+		/// or gets repriced, it is purchased automatically. Once executed, the part of the budget
+		/// that wasn't spent is returned to the buyer.
+		///
+		/// ## Arguments:
+		/// - `asset`: The asset in which the region has to be priced.
+		/// - `max_timeslice_price`: The maximum price per timeslice the caller is willing to pay.
+		/// - `min_occupancy`: The minimum fraction of the core the region has to occupy, in the
+		///   same unit as the core occupancy of Coretime orders.
+		/// - `begin`: The earliest timeslice at which the region can begin.
+		/// - `end`: The latest timeslice at which the region can end.
+		/// - `budget`: The maximum total price the caller is willing to pay for the region.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::create_buy_order())]
+		pub fn create_buy_order(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			max_timeslice_price: BalanceOf<T>,
+			min_occupancy: PartsOf57600,
+			begin: Timeslice,
+			end: Timeslice,
+			budget: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::SupportedAssets::contains(&asset), Error::<T>::UnsupportedAsset);
+			ensure!(
+				begin < end &&
+					end > Self::current_timeslice() &&
+					min_occupancy <= 57600 &&
+					!budget.is_zero(),
+				Error::<T>::InvalidBuyOrder
+			);
+			ensure!(BuyOrders::<T>::count() < T::MaxBuyOrders::get(), Error::<T>::TooManyBuyOrders);
+
+			T::Assets::transfer(asset, &who, &Self::account_id(), budget)?;
+
+			let order_id = NextBuyOrderId::<T>::get();
+			BuyOrders::<T>::insert(
+				order_id,
+				BuyOrder {
+					buyer: who.clone(),
+					asset,
+					max_timeslice_price,
+					min_occupancy,
+					begin,
+					end,
+					budget,
+				},
+			);
+			NextBuyOrderId::<T>::put(order_id.saturating_add(1));
+
+			Self::deposit_event(Event::BuyOrderCreated {
+				order_id,
+				buyer: who,
+				asset,
+				max_timeslice_price,
+				min_occupancy,
+				begin,
+				end,
+				budget,
+			});
+
+			Ok(())
+		}
+
+		/// Extrinsic for purchasing the cheapest listed region matching the requirements.
+		///
+		/// The price is paid in the native currency, listings priced in other assets are
+		/// converted. At most `MaxSweepCandidates` listings are considered.
+		///
+		/// ## Arguments:
+		/// - `requirements`: The requirements the region has to satisfy. These follow the same
+		///   rules as the requirements of Coretime orders.
+		/// - `max_total`: The maximum price the caller is willing to pay for the region.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::sweep(T::MaxSweepCandidates::get()))]
+		pub fn sweep(
+			origin: OriginFor<T>,
+			requirements: Requirements,
+			max_total: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let region_id = Self::cheapest_matching_listing(&who, &requirements, max_total)
+				.ok_or(Error::<T>::NoMatchingListing)?;
+			Self::do_purchase(who, region_id, max_total, Some(T::NativeAssetId::get()))?;
+
+			Ok(())
+		}
+
+		/// Extrinsic for cancelling a buy order.
+		///
+		/// The escrowed budget is returned to the buyer. Once the order can no longer be matched,
+		/// anyone can cancel it.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The buy order to cancel.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::cancel_buy_order())]
+		pub fn cancel_buy_order(origin: OriginFor<T>, order_id: BuyOrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::Purchased { region_id, buyer: who, total_price: price });
+			let order = BuyOrders::<T>::get(order_id).ok_or(Error::<T>::UnknownBuyOrder)?;
+			if Self::current_timeslice() < order.end {
+				ensure!(who == order.buyer, Error::<T>::NotAllowed);
+			}
+
+			BuyOrders::<T>::remove(order_id);
+			T::Assets::transfer(order.asset, &Self::account_id(), &order.buyer, order.budget)?;
+
+			Self::deposit_event(Event::BuyOrderCancelled { order_id });
 
 			Ok(())
 		}
@@ -296,18 +1003,683 @@ pub mod pallet {
 			record: RegionRecordOf<T>,
 			timeslice_price: BalanceOf<T>,
 		) -> BalanceOf<T> {
+			timeslice_price.saturating_mul(Self::remaining_duration(region_id, &record).into())
+		}
+
+		/// Returns the number of timeslices for which the region is still usable.
+		pub(crate) fn remaining_duration(
+			region_id: RegionId,
+			record: &RegionRecordOf<T>,
+		) -> Timeslice {
 			let current_timeslice = Self::current_timeslice();
-			let duration = record.end.saturating_sub(region_id.begin);
 
 			if current_timeslice < region_id.begin {
 				// The region didn't start yet, so there is no value lost.
-				let price = timeslice_price.saturating_mul(duration.into());
+				return record.end.saturating_sub(region_id.begin);
+			}
+
+			record.end.saturating_sub(current_timeslice)
+		}
+
+		/// Records a purchase in the price history and updates the average price of the ongoing
+		/// bulk period.
+		///
+		/// The price is converted to the native currency and normalised to the price of a single
+		/// timeslice of a full core. Purchases which cannot be converted are not recorded.
+		pub(crate) fn record_price(
+			region_id: RegionId,
+			duration: Timeslice,
+			asset: T::AssetId,
+			price: BalanceOf<T>,
+		) {
+			let price = if asset == T::NativeAssetId::get() {
+				price
+			} else {
+				match T::AssetConverter::from_asset_balance(price, asset) {
+					Ok(price) => price,
+					Err(_) => return,
+				}
+			};
+
+			let occupancy = region_id.mask.count_ones().saturating_mul(duration);
+			if occupancy.is_zero() {
+				return
+			}
+			let price = price.saturating_mul(CORE_MASK_BITS.into()) / occupancy.into();
+
+			let timeslice = Self::current_timeslice();
+			PriceHistory::<T>::mutate(|history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				// Can't fail since we made room for the new entry.
+				let _ = history.try_push(PricePoint { timeslice, price });
+			});
+
+			let period = timeslice / T::BulkPeriod::get();
+			PeriodPrices::<T>::mutate(period, |maybe_period_price| match maybe_period_price {
+				Some(period_price) => {
+					let elapsed = timeslice.saturating_sub(period_price.last_update);
+					period_price.cumulative = period_price
+						.cumulative
+						.saturating_add(period_price.last_price.saturating_mul(elapsed.into()));
+					period_price.last_price = price;
+					period_price.last_update = timeslice;
+				},
+				None =>
+					*maybe_period_price = Some(PeriodPrice {
+						first_update: timeslice,
+						last_update: timeslice,
+						last_price: price,
+						cumulative: Zero::zero(),
+					}),
+			});
+		}
+
+		/// Checks whether `who` can purchase the region for at most `max_price`.
+		///
+		/// Returns the listing along with the asset and the amount to be paid for the region.
+		pub(crate) fn quote_purchase(
+			who: &T::AccountId,
+			region_id: RegionId,
+			max_price: BalanceOf<T>,
+			payment_asset: Option<T::AssetId>,
+		) -> Result<PurchaseQuoteOf<T>, DispatchError> {
+			let listing = Listings::<T>::get(region_id).ok_or(Error::<T>::NotListed)?;
+			let record = T::Regions::record(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
+
+			ensure!(
+				*who != listing.seller &&
+					!listing.sale_recipients.iter().any(|(recipient, _)| recipient == who),
+				Error::<T>::NotAllowed
+			);
+			if let Some(allowed_buyers) = &listing.allowed_buyers {
+				ensure!(allowed_buyers.contains(who), Error::<T>::NotAllowed);
+			}
+			if let Some(expires_at) = listing.expires_at {
+				ensure!(Self::current_timeslice() < expires_at, Error::<T>::ListingExpired);
+			}
+			ensure!(!Self::is_stale(region_id, &listing, &record), Error::<T>::ListingStale);
+
+			let price = Self::calculate_region_price(region_id, record, listing.timeslice_price);
+			let (asset, price) = Self::payment_amount(&listing, price, payment_asset)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+
+			Ok((listing, asset, price))
+		}
+
+		/// Purchases a listed region on behalf of `who`.
+		///
+		/// Returns the asset and the amount paid for the region.
+		pub(crate) fn do_purchase(
+			who: T::AccountId,
+			region_id: RegionId,
+			max_price: BalanceOf<T>,
+			payment_asset: Option<T::AssetId>,
+		) -> Result<(T::AssetId, BalanceOf<T>), DispatchError> {
+			let (listing, asset, price) =
+				Self::quote_purchase(&who, region_id, max_price, payment_asset)?;
+
+			// The market fee is deducted from the price, the rest goes to the sale recipients.
+			let fee = MarketFee::<T>::get() * price;
+			let payments = Self::pay_sale_recipients(
+				&who,
+				region_id,
+				asset,
+				&listing.sale_recipients,
+				price.saturating_sub(fee),
+			)?;
+			if !fee.is_zero() {
+				T::FeeDestination::handle(&who, asset, fee)?;
+			}
+
+			let remaining_duration = T::Regions::record(&region_id.into())
+				.map_or(0, |record| Self::remaining_duration(region_id, &record));
+			Self::record_price(region_id, remaining_duration, asset, price);
+
+			// Remove the region from sale:
+			Self::release_deposit(&listing)?;
+			Self::remove_listing(region_id, &listing)?;
+			Self::deposit_event(Event::Unlisted {
+				region_id,
+				who: Some(who.clone()),
+				reason: UnlistReason::Purchased,
+			});
+
+			T::Regions::transfer(&region_id.into(), &who)?;
 
-				return price;
+			Self::deposit_event(Event::Purchased {
+				region_id,
+				buyer: who.clone(),
+				seller: listing.seller,
+				sale_recipients: payments,
+				asset,
+				total_price: price,
+				timeslice_price: listing.timeslice_price,
+				remaining_duration,
+				fee,
+			});
+
+			Ok((asset, price))
+		}
+
+		/// Returns the cheapest listed region `who` can purchase for at most `max_total` in the
+		/// native currency, which satisfies the requirements.
+		///
+		/// The listing index is searched starting from the regions ending the soonest after the
+		/// required end. At most `MaxSweepCandidates` index entries are read, so with more listings
+		/// lasting long enough a cheaper region may be missed.
+		pub(crate) fn cheapest_matching_listing(
+			who: &T::AccountId,
+			requirements: &Requirements,
+			max_total: BalanceOf<T>,
+		) -> Option<RegionId> {
+			let native_asset = T::NativeAssetId::get();
+			let min_end = requirements.end.max(Self::current_timeslice().saturating_add(1));
+			let mut cheapest: Option<(RegionId, BalanceOf<T>)> = None;
+
+			let mut start_key = Self::index_prefix(min_end);
+			for _ in 0..T::MaxSweepCandidates::get() {
+				let Some((end, begin, region_id)) =
+					ListingIndex::<T>::iter_keys_from(start_key).next()
+				else {
+					break
+				};
+
+				if begin.0 > requirements.begin {
+					// The other regions with the same end begin even later, so we skip to the
+					// regions ending after this one.
+					match end.0.checked_add(1) {
+						Some(next_end) => start_key = Self::index_prefix(next_end),
+						None => break,
+					}
+					continue
+				}
+				start_key = ListingIndex::<T>::hashed_key_for((end, begin, region_id));
+
+				if core_occupancy(region_id.mask.count_ones()) < requirements.core_occupancy.into()
+				{
+					continue
+				}
+				let Ok((_, _, price)) =
+					Self::quote_purchase(who, region_id, max_total, Some(native_asset))
+				else {
+					continue
+				};
+				if cheapest.map_or(true, |(_, cheapest_price)| price < cheapest_price) {
+					cheapest = Some((region_id, price));
+				}
 			}
 
-			let remaining_timeslices = record.end.saturating_sub(current_timeslice);
-			timeslice_price.saturating_mul(remaining_timeslices.into())
+			cheapest.map(|(region_id, _)| region_id)
+		}
+
+		/// Returns the key of a listed region in the listing index.
+		pub(crate) fn index_key(
+			region_id: RegionId,
+			end: Timeslice,
+		) -> (OrderedTimeslice, OrderedTimeslice, RegionId) {
+			(OrderedTimeslice(end), OrderedTimeslice(region_id.begin), region_id)
+		}
+
+		/// Returns the raw storage key preceding the index entries of all the regions ending at
+		/// or after `end`.
+		fn index_prefix(end: Timeslice) -> Vec<u8> {
+			let mut key = ListingIndex::<T>::final_prefix().to_vec();
+			key.extend(OrderedTimeslice(end).encode());
+			key
+		}
+
+		/// Executes the first open buy order matching the listing of `region_id`, if any.
+		///
+		/// At most `MaxAutoPurchases` buy orders are executed per block. A buy order which fails
+		/// to execute is left untouched.
+		pub(crate) fn match_buy_orders(region_id: RegionId) {
+			let block_number = frame_system::Pallet::<T>::block_number();
+			let (last_block, executed) = AutoPurchases::<T>::get();
+			let executed = if last_block == block_number { executed } else { 0 };
+			if executed >= T::MaxAutoPurchases::get() {
+				return
+			}
+
+			let Some(listing) = Listings::<T>::get(region_id) else { return };
+			let Some(record) = T::Regions::record(&region_id.into()) else { return };
+
+			for (order_id, order) in BuyOrders::<T>::iter() {
+				let matches = order.asset == listing.asset &&
+					listing.timeslice_price <= order.max_timeslice_price &&
+					core_occupancy(region_id.mask.count_ones()) >= order.min_occupancy.into() &&
+					region_id.begin >= order.begin &&
+					record.end <= order.end;
+				if !matches {
+					continue
+				}
+
+				let result = with_storage_layer::<_, DispatchError, _>(|| {
+					Self::execute_buy_order(order_id, &order, region_id)
+				});
+				if result.is_ok() {
+					AutoPurchases::<T>::put((block_number, executed.saturating_add(1)));
+					return
+				}
+			}
+		}
+
+		/// Purchases `region_id` with the budget of a buy order and removes the order.
+		fn execute_buy_order(
+			order_id: BuyOrderId,
+			order: &BuyOrderOf<T>,
+			region_id: RegionId,
+		) -> DispatchResult {
+			BuyOrders::<T>::remove(order_id);
+
+			// The budget is returned to the buyer who then pays for the region as usual.
+			T::Assets::transfer(order.asset, &Self::account_id(), &order.buyer, order.budget)?;
+			let (_, price) =
+				Self::do_purchase(order.buyer.clone(), region_id, order.budget, Some(order.asset))?;
+
+			Self::deposit_event(Event::BuyOrderExecuted { order_id, region_id, price });
+
+			Ok(())
+		}
+
+		/// Splits `amount` among the sale recipients according to their shares.
+		///
+		/// The payments are rounded down, the remainder goes to the last recipient. This way the
+		/// recipients never get more than `amount` in total. Returns the amounts paid.
+		fn pay_sale_recipients(
+			who: &T::AccountId,
+			region_id: RegionId,
+			asset: T::AssetId,
+			recipients: &SaleRecipients<T::AccountId>,
+			amount: BalanceOf<T>,
+		) -> Result<SalePayments<T::AccountId, BalanceOf<T>>, DispatchError> {
+			let mut payments = SalePayments::<T::AccountId, BalanceOf<T>>::default();
+			let mut remaining = amount;
+			for (index, (recipient, share)) in recipients.iter().enumerate() {
+				let payment = if index == recipients.len() - 1 {
+					remaining
+				} else {
+					share.mul_floor(amount).min(remaining)
+				};
+				if !payment.is_zero() {
+					T::Assets::transfer(asset, who, recipient, payment)?;
+				}
+				remaining = remaining.saturating_sub(payment);
+
+				if recipients.len() > 1 {
+					Self::deposit_event(Event::RegionSoldPartially {
+						region_id,
+						sale_recipient: recipient.clone(),
+						share: *share,
+						amount: payment,
+					});
+				}
+				// Can't fail, there are as many payments as recipients.
+				let _ = payments.try_push((recipient.clone(), payment));
+			}
+
+			Ok(payments)
+		}
+
+		/// Returns the asset and the amount for which the region can currently be purchased.
+		///
+		/// Returns `None` if the region is not listed, if the listing expired or if the price
+		/// cannot be paid in `payment_asset`.
+		pub fn quote(
+			region_id: RegionId,
+			payment_asset: Option<T::AssetId>,
+		) -> Option<(T::AssetId, BalanceOf<T>)> {
+			let listing = Listings::<T>::get(region_id)?;
+			let record = T::Regions::record(&region_id.into())?;
+			if Self::is_expired(region_id, &listing, &record, Self::current_timeslice()) {
+				return None
+			}
+
+			let price = Self::calculate_region_price(region_id, record, listing.timeslice_price);
+			Self::payment_amount(&listing, price, payment_asset).ok()
+		}
+
+		/// Returns up to `limit` listings which are not expired, starting after `start_after`.
+		///
+		/// At most `MAX_LISTINGS_PAGE` listings are returned and at most `MAX_SCANNED_LISTINGS`
+		/// listings are checked. Listings are returned in storage order, along with the region to
+		/// use as `start_after` for getting the next page. The latter is `None` once all the
+		/// listings were checked.
+		pub fn active_listings(
+			start_after: Option<RegionId>,
+			limit: u32,
+		) -> (Vec<(RegionId, ListingOf<T>)>, Option<RegionId>) {
+			let limit = limit.min(MAX_LISTINGS_PAGE) as usize;
+			if limit == 0 {
+				return (Vec::new(), start_after)
+			}
+
+			let mut listings = match start_after {
+				Some(last) => Listings::<T>::iter_from(Listings::<T>::hashed_key_for(last)),
+				None => Listings::<T>::iter(),
+			};
+
+			let current_timeslice = Self::current_timeslice();
+			let mut page = Vec::new();
+			let mut cursor = start_after;
+			for _ in 0..MAX_SCANNED_LISTINGS {
+				let Some((region_id, listing)) = listings.next() else { return (page, None) };
+				cursor = Some(region_id);
+
+				let active = T::Regions::record(&region_id.into()).map_or(false, |record| {
+					!Self::is_expired(region_id, &listing, &record, current_timeslice)
+				});
+				if active {
+					page.push((region_id, listing));
+					if page.len() == limit {
+						break
+					}
+				}
+			}
+
+			(page, cursor)
+		}
+
+		/// Returns all the listings of `seller`.
+		pub fn listings_of(seller: &T::AccountId) -> Vec<(RegionId, ListingOf<T>)> {
+			SellerListings::<T>::get(seller)
+				.into_iter()
+				.filter_map(|region_id| Listings::<T>::get(region_id).map(|l| (region_id, l)))
+				.collect()
+		}
+
+		/// Returns the asset and the amount the buyer has to pay for the listing.
+		///
+		/// If the buyer pays in an asset other than the one the region is priced in, the price
+		/// gets converted to the native currency.
+		pub(crate) fn payment_amount(
+			listing: &ListingOf<T>,
+			price: BalanceOf<T>,
+			payment_asset: Option<T::AssetId>,
+		) -> Result<(T::AssetId, BalanceOf<T>), DispatchError> {
+			let asset = match payment_asset {
+				Some(asset) if asset != listing.asset => asset,
+				_ => return Ok((listing.asset, price)),
+			};
+
+			ensure!(asset == T::NativeAssetId::get(), Error::<T>::UnsupportedConversion);
+			let price = T::AssetConverter::from_asset_balance(price, listing.asset)
+				.map_err(|_| Error::<T>::ConversionFailed)?;
+
+			Ok((asset, price))
+		}
+
+		/// Removes a listing from the market and unlocks the region.
+		///
+		/// The listing deposit has to be released or slashed separately.
+		pub(crate) fn remove_listing(
+			region_id: RegionId,
+			listing: &ListingOf<T>,
+		) -> DispatchResult {
+			Listings::<T>::remove(region_id);
+			if let Some(record) = T::Regions::record(&region_id.into()) {
+				ListingIndex::<T>::remove(Self::index_key(region_id, record.end));
+			}
+			SellerListings::<T>::mutate_exists(&listing.seller, |maybe_listings| {
+				if let Some(listings) = maybe_listings {
+					listings.retain(|id| *id != region_id);
+					if listings.is_empty() {
+						*maybe_listings = None;
+					}
+				}
+			});
+			T::Regions::unlock(&region_id.into(), None)?;
+
+			Ok(())
+		}
+
+		/// Returns the listing deposit to the seller.
+		pub(crate) fn release_deposit(listing: &ListingOf<T>) -> DispatchResult {
+			if !listing.deposit.is_zero() {
+				T::Currency::release(
+					&HoldReason::ListingDeposit.into(),
+					&listing.seller,
+					listing.deposit,
+					Precision::BestEffort,
+				)?;
+			}
+
+			Ok(())
+		}
+
+		/// Slashes the listing deposit of an expired listing removed by a `cleaner`.
+		///
+		/// The cleaner gets rewarded with a part of the deposit, the rest goes to the treasury.
+		pub(crate) fn slash_deposit(
+			region_id: RegionId,
+			listing: &ListingOf<T>,
+			cleaner: &T::AccountId,
+		) -> DispatchResult {
+			if listing.deposit.is_zero() {
+				return Ok(())
+			}
+
+			let reason = HoldReason::ListingDeposit.into();
+			let reward = T::Currency::transfer_on_hold(
+				&reason,
+				&listing.seller,
+				cleaner,
+				T::CleanupReward::get() * listing.deposit,
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Force,
+			)?;
+			let amount = T::Currency::transfer_on_hold(
+				&reason,
+				&listing.seller,
+				&T::TreasuryAccount::get(),
+				listing.deposit.saturating_sub(reward),
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Force,
+			)?;
+
+			Self::deposit_event(Event::ListingDepositSlashed {
+				region_id,
+				seller: listing.seller.clone(),
+				amount,
+				reward,
+			});
+
+			Ok(())
+		}
+
+		/// Removes a swap proposal, unlocking both regions and returning the top-up to the
+		/// proposer.
+		pub(crate) fn remove_swap(offered: RegionId, swap: &SwapOf<T>) -> DispatchResult {
+			Swaps::<T>::remove(offered);
+			T::Regions::unlock(&offered.into(), None)?;
+			// The requested region may have been dropped once it expired.
+			if T::Regions::region(&swap.requested.into()).is_some() {
+				T::Regions::unlock(&swap.requested.into(), None)?;
+			}
+			if let Some((asset, amount)) = swap.top_up {
+				T::Assets::transfer(asset, &Self::account_id(), &swap.proposer, amount)?;
+			}
+
+			Ok(())
+		}
+
+		/// Returns whether the swap proposal or either of the swapped regions expired.
+		///
+		/// Either way the swap can no longer be accepted.
+		pub(crate) fn is_swap_expired(
+			offered: RegionId,
+			swap: &SwapOf<T>,
+			current_timeslice: Timeslice,
+		) -> bool {
+			let region_expired = |region_id: RegionId| {
+				T::Regions::record(&region_id.into())
+					.map_or(false, |record| record.end <= current_timeslice)
+			};
+
+			swap.expires_at <= current_timeslice ||
+				region_expired(offered) ||
+				region_expired(swap.requested)
+		}
+
+		/// Returns whether the listing or the listed region expired.
+		///
+		/// Stale listings are considered expired as well.
+		pub(crate) fn is_expired(
+			region_id: RegionId,
+			listing: &ListingOf<T>,
+			record: &RegionRecordOf<T>,
+			current_timeslice: Timeslice,
+		) -> bool {
+			Self::expiry_reason(region_id, listing, record, current_timeslice).is_some()
+		}
+
+		/// Returns why the listing can no longer be purchased, or `None` if it still can be.
+		pub(crate) fn expiry_reason(
+			region_id: RegionId,
+			listing: &ListingOf<T>,
+			record: &RegionRecordOf<T>,
+			current_timeslice: Timeslice,
+		) -> Option<UnlistReason> {
+			let listing_expired = listing.expires_at.map_or(false, |e| e <= current_timeslice);
+			if listing_expired || record.end < current_timeslice {
+				Some(UnlistReason::Expired)
+			} else if Self::is_stale(region_id, listing, record) {
+				Some(UnlistReason::Stale)
+			} else {
+				None
+			}
+		}
+
+		/// Returns whether fewer timeslices than the seller's minimum remain in the region.
+		pub(crate) fn is_stale(
+			region_id: RegionId,
+			listing: &ListingOf<T>,
+			record: &RegionRecordOf<T>,
+		) -> bool {
+			Self::remaining_duration(region_id, record) < listing.min_remaining_timeslices
+		}
+
+		/// Unlists expired and stale listings until `weight_limit` is reached.
+		///
+		/// The deposits of the swept listings are returned to the sellers, since no one had to
+		/// pay for removing them. The sweep continues from where it stopped in the previous block,
+		/// so all listings get checked eventually. Returns the consumed weight.
+		pub(crate) fn sweep_expired_listings(weight_limit: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(weight_limit);
+			// Reading and updating the sweep cursor.
+			if meter.try_consume(T::DbWeight::get().reads_writes(2, 1)).is_err() {
+				return meter.consumed()
+			}
+
+			// Reading the listing and the region record.
+			let check_weight = T::DbWeight::get().reads(2);
+			let removal_weight = T::WeightInfo::remove_expired_listing();
+
+			let current_timeslice = Self::current_timeslice();
+			let mut listings = match SweepCursor::<T>::get() {
+				Some(last) => Listings::<T>::iter_from(Listings::<T>::hashed_key_for(last)),
+				None => Listings::<T>::iter(),
+			};
+
+			let mut cursor = SweepCursor::<T>::get();
+			while meter.can_consume(check_weight.saturating_add(removal_weight)) {
+				let Some((region_id, listing)) = listings.next() else {
+					// We went through all the listings, start from the beginning next time.
+					cursor = None;
+					break
+				};
+				meter.consume(check_weight);
+				cursor = Some(region_id);
+
+				// A listing without a record cannot be checked for expiry, so we skip it.
+				let Some(record) = T::Regions::record(&region_id.into()) else { continue };
+				let Some(reason) =
+					Self::expiry_reason(region_id, &listing, &record, current_timeslice)
+				else {
+					continue
+				};
+
+				meter.consume(removal_weight);
+				let removal = with_storage_layer::<_, DispatchError, _>(|| {
+					Self::release_deposit(&listing)?;
+					Self::remove_listing(region_id, &listing)
+				});
+				if let Err(err) = removal {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to remove expired listing {:?}: {:?}",
+						region_id,
+						err
+					);
+					continue
+				}
+				Self::deposit_event(Event::Unlisted { region_id, who: None, reason });
+			}
+			SweepCursor::<T>::set(cursor);
+
+			meter.consumed()
+		}
+
+		/// Cancels expired swap proposals until `weight_limit` is reached.
+		///
+		/// Like the listing sweep, it continues from where it stopped in the previous block.
+		/// Returns the consumed weight.
+		pub(crate) fn sweep_expired_swaps(weight_limit: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(weight_limit);
+			// Reading and updating the sweep cursor.
+			if meter.try_consume(T::DbWeight::get().reads_writes(2, 1)).is_err() {
+				return meter.consumed()
+			}
+
+			// Reading the swap and the records of both regions.
+			let check_weight = T::DbWeight::get().reads(3);
+			let removal_weight = T::WeightInfo::cancel_swap();
+
+			let current_timeslice = Self::current_timeslice();
+			let mut swaps = match SwapSweepCursor::<T>::get() {
+				Some(last) => Swaps::<T>::iter_from(Swaps::<T>::hashed_key_for(last)),
+				None => Swaps::<T>::iter(),
+			};
+
+			let mut cursor = SwapSweepCursor::<T>::get();
+			while meter.can_consume(check_weight.saturating_add(removal_weight)) {
+				let Some((offered, swap)) = swaps.next() else {
+					// We went through all the swaps, start from the beginning next time.
+					cursor = None;
+					break
+				};
+				meter.consume(check_weight);
+				cursor = Some(offered);
+
+				if !Self::is_swap_expired(offered, &swap, current_timeslice) {
+					continue
+				}
+
+				meter.consume(removal_weight);
+				let removal =
+					with_storage_layer::<_, DispatchError, _>(|| Self::remove_swap(offered, &swap));
+				if let Err(err) = removal {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to remove expired swap {:?}: {:?}",
+						offered,
+						err
+					);
+					continue
+				}
+				Self::deposit_event(Event::SwapCancelled { offered });
+			}
+			SwapSweepCursor::<T>::set(cursor);
+
+			meter.consumed()
+		}
+
+		/// The account holding the swap top-ups and the buy order budgets.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
 		}
 
 		pub(crate) fn current_timeslice() -> Timeslice {
@@ -317,3 +1689,69 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> CoretimePriceOracle<BalanceOf<T>> for Pallet<T> {
+	fn latest_price() -> Option<BalanceOf<T>> {
+		PriceHistory::<T>::get().last().map(|point| point.price)
+	}
+
+	fn average_price(period: u32) -> Option<BalanceOf<T>> {
+		let period_price = PeriodPrices::<T>::get(period)?;
+
+		// The latest price is valid until now, or until the end of the period if it is over.
+		let period_end = period.saturating_add(1).saturating_mul(T::BulkPeriod::get());
+		let end = Self::current_timeslice().min(period_end).max(period_price.last_update);
+
+		let elapsed = end.saturating_sub(period_price.first_update);
+		if elapsed.is_zero() {
+			return Some(period_price.last_price)
+		}
+
+		let last_elapsed = end.saturating_sub(period_price.last_update);
+		let total = period_price
+			.cumulative
+			.saturating_add(period_price.last_price.saturating_mul(last_elapsed.into()));
+		Some(total / elapsed.into())
+	}
+}
+
+/// Purchases listed regions through the market, paying in `PaymentAsset`.
+pub struct PurchaseWith<T, PaymentAsset>(core::marker::PhantomData<(T, PaymentAsset)>);
+
+impl<T: Config, PaymentAsset: Get<T::AssetId>> RegionMarket<T::AccountId, BalanceOf<T>>
+	for PurchaseWith<T, PaymentAsset>
+{
+	fn purchase(
+		buyer: &T::AccountId,
+		region_id: RegionId,
+		max_price: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let (_, price) = Pallet::<T>::do_purchase(
+			buyer.clone(),
+			region_id,
+			max_price,
+			Some(PaymentAsset::get()),
+		)?;
+		Ok(price)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn list(
+		seller: &T::AccountId,
+		region_id: RegionId,
+		timeslice_price: BalanceOf<T>,
+	) -> frame_support::pallet_prelude::DispatchResult {
+		use frame_support::traits::fungible::Mutate;
+
+		let deposit = T::ListingDeposit::get().saturating_add(T::Currency::minimum_balance());
+		T::Currency::mint_into(seller, deposit)?;
+
+		Pallet::<T>::list_region(
+			frame_system::RawOrigin::Signed(seller.clone()).into(),
+			region_id,
+			timeslice_price,
+			PaymentAsset::get(),
+			Default::default(),
+		)
+	}
+}
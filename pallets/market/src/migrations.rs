@@ -0,0 +1,536 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations of the market pallet.
+//!
+//! Each version corresponds to a change of the listing layout or of the listing indexes. The
+//! migrations have to be applied in order.
+
+use crate::*;
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+	migrations::VersionedMigration,
+	pallet_prelude::{OptionQuery, Weight},
+	storage::with_storage_layer,
+	storage_alias,
+	traits::{fungible::Mutate, tokens::Preservation, OnRuntimeUpgrade},
+	Blake2_128Concat,
+};
+use sp_core::bounded_vec;
+
+/// Returns the number of listings, to be checked after the migration.
+#[cfg(feature = "try-runtime")]
+fn listing_count<T: Config>() -> Vec<u8> {
+	(Listings::<T>::iter_keys().count() as u32).encode()
+}
+
+/// Ensures no listing got lost in the migration.
+#[cfg(feature = "try-runtime")]
+fn ensure_listing_count(state: Vec<u8>, current: usize) -> Result<(), sp_runtime::TryRuntimeError> {
+	let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre upgrade state")?;
+	ensure!(current as u32 == count, "Listings got lost in the migration");
+	Ok(())
+}
+
+pub mod v0 {
+	use super::*;
+
+	/// The initial listing layout.
+	#[derive(Encode, Decode)]
+	pub struct Listing<AccountId, Balance> {
+		pub seller: AccountId,
+		pub timeslice_price: Balance,
+		pub sale_recipient: AccountId,
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// The listing layout once listings got an expiry and a deposit.
+	#[derive(Encode, Decode)]
+	pub struct Listing<AccountId, Balance> {
+		pub seller: AccountId,
+		pub timeslice_price: Balance,
+		pub sale_recipient: AccountId,
+		pub expires_at: Option<Timeslice>,
+		pub deposit: Balance,
+	}
+
+	#[storage_alias]
+	pub type Listings<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		RegionId,
+		Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Adds the expiry and the deposit to the listings, and indexes them by seller.
+	///
+	/// The migrated listings never expire and hold no deposit, since none was taken when they
+	/// got listed.
+	pub struct VersionUncheckedMigrateToV1<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Listings::<T>::translate::<v0::Listing<T::AccountId, BalanceOf<T>>, _>(
+				|region_id, old| {
+					migrated.saturating_inc();
+
+					let indexed = SellerListings::<T>::mutate(&old.seller, |listings| {
+						listings.try_push(region_id).is_ok()
+					});
+					if !indexed {
+						log::error!(
+							target: LOG_TARGET,
+							"Too many listings of {:?} to index {:?}",
+							old.seller,
+							region_id
+						);
+					}
+
+					Some(Listing {
+						seller: old.seller,
+						timeslice_price: old.timeslice_price,
+						sale_recipient: old.sale_recipient,
+						expires_at: None,
+						deposit: Zero::zero(),
+					})
+				},
+			);
+
+			log::info!(target: LOG_TARGET, "Migrated {} listings to v1", migrated);
+			// Reading and writing the listing and the seller listings.
+			T::DbWeight::get().reads_writes(migrated.saturating_mul(2), migrated.saturating_mul(2))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, Listings::<T>::iter_values().count())?;
+			ensure!(
+				Listings::<T>::iter().all(|(region_id, listing)| {
+					SellerListings::<T>::get(&listing.seller).contains(&region_id)
+				}),
+				"Listings are missing from the seller listings"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrates the market storage from version 0 to 1.
+	pub type MigrateToV1<T> = VersionedMigration<
+		0,
+		1,
+		VersionUncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The listing layout once listings got priced in assets.
+	#[derive(Encode, Decode)]
+	pub struct Listing<AccountId, Balance, AssetId> {
+		pub seller: AccountId,
+		pub timeslice_price: Balance,
+		pub asset: AssetId,
+		pub sale_recipient: AccountId,
+		pub expires_at: Option<Timeslice>,
+		pub deposit: Balance,
+	}
+
+	#[storage_alias]
+	pub type Listings<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		RegionId,
+		Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as Config>::AssetId>,
+		OptionQuery,
+	>;
+
+	/// Adds the asset to the listings.
+	///
+	/// Listings were priced in the relay chain currency before, so `RelayAssetId` has to be the
+	/// asset id of the relay chain currency.
+	pub struct VersionUncheckedMigrateToV2<T, RelayAssetId>(
+		core::marker::PhantomData<(T, RelayAssetId)>,
+	);
+	impl<T: Config, RelayAssetId: Get<T::AssetId>> OnRuntimeUpgrade
+		for VersionUncheckedMigrateToV2<T, RelayAssetId>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Listings::<T>::translate::<v1::Listing<T::AccountId, BalanceOf<T>>, _>(|_, old| {
+				migrated.saturating_inc();
+				Some(Listing {
+					seller: old.seller,
+					timeslice_price: old.timeslice_price,
+					asset: RelayAssetId::get(),
+					sale_recipient: old.sale_recipient,
+					expires_at: old.expires_at,
+					deposit: old.deposit,
+				})
+			});
+
+			log::info!(target: LOG_TARGET, "Migrated {} listings to v2", migrated);
+			T::DbWeight::get().reads_writes(migrated, migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, Listings::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the market storage from version 1 to 2.
+	pub type MigrateToV2<T, RelayAssetId> = VersionedMigration<
+		1,
+		2,
+		VersionUncheckedMigrateToV2<T, RelayAssetId>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// The listing layout once listings could be restricted to a set of buyers.
+	#[derive(Encode, Decode)]
+	pub struct Listing<AccountId, Balance, AssetId> {
+		pub seller: AccountId,
+		pub timeslice_price: Balance,
+		pub asset: AssetId,
+		pub sale_recipient: AccountId,
+		pub expires_at: Option<Timeslice>,
+		pub deposit: Balance,
+		pub allowed_buyers: Option<AllowedBuyers<AccountId>>,
+	}
+
+	#[storage_alias]
+	pub type Listings<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		RegionId,
+		Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as Config>::AssetId>,
+		OptionQuery,
+	>;
+
+	/// Adds the allowed buyers to the listings. The migrated listings stay public.
+	pub struct VersionUncheckedMigrateToV3<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Listings::<T>::translate::<v2::Listing<T::AccountId, BalanceOf<T>, T::AssetId>, _>(
+				|_, old| {
+					migrated.saturating_inc();
+					Some(Listing {
+						seller: old.seller,
+						timeslice_price: old.timeslice_price,
+						asset: old.asset,
+						sale_recipient: old.sale_recipient,
+						expires_at: old.expires_at,
+						deposit: old.deposit,
+						allowed_buyers: None,
+					})
+				},
+			);
+
+			log::info!(target: LOG_TARGET, "Migrated {} listings to v3", migrated);
+			T::DbWeight::get().reads_writes(migrated, migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, Listings::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the market storage from version 2 to 3.
+	pub type MigrateToV3<T> = VersionedMigration<
+		2,
+		3,
+		VersionUncheckedMigrateToV3<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// The listing layout once the sale proceeds could be split among several recipients.
+	#[derive(Encode, Decode)]
+	pub struct Listing<AccountId, Balance, AssetId> {
+		pub seller: AccountId,
+		pub timeslice_price: Balance,
+		pub asset: AssetId,
+		pub sale_recipients: SaleRecipients<AccountId>,
+		pub expires_at: Option<Timeslice>,
+		pub deposit: Balance,
+		pub allowed_buyers: Option<AllowedBuyers<AccountId>>,
+	}
+
+	#[storage_alias]
+	pub type Listings<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		RegionId,
+		Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as Config>::AssetId>,
+		OptionQuery,
+	>;
+
+	/// Replaces the sale recipient of the listings with a single recipient receiving the whole
+	/// payment.
+	pub struct VersionUncheckedMigrateToV4<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Listings::<T>::translate::<v3::Listing<T::AccountId, BalanceOf<T>, T::AssetId>, _>(
+				|_, old| {
+					migrated.saturating_inc();
+					Some(Listing {
+						seller: old.seller,
+						timeslice_price: old.timeslice_price,
+						asset: old.asset,
+						sale_recipients: bounded_vec![(old.sale_recipient, Permill::one())],
+						expires_at: old.expires_at,
+						deposit: old.deposit,
+						allowed_buyers: old.allowed_buyers,
+					})
+				},
+			);
+
+			log::info!(target: LOG_TARGET, "Migrated {} listings to v4", migrated);
+			T::DbWeight::get().reads_writes(migrated, migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, Listings::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the market storage from version 3 to 4.
+	pub type MigrateToV4<T> = VersionedMigration<
+		3,
+		4,
+		VersionUncheckedMigrateToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Adds the listed regions to the listing index.
+	///
+	/// Listings of regions without a record can't be purchased anyway and are left out.
+	pub struct VersionUncheckedMigrateToV5<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut indexed = 0u64;
+			let mut listings = 0u64;
+			for region_id in v4::Listings::<T>::iter_keys() {
+				listings.saturating_inc();
+				if let Some(record) = T::Regions::record(&region_id.into()) {
+					indexed.saturating_inc();
+					ListingIndex::<T>::insert(Pallet::<T>::index_key(region_id, record.end), ());
+				}
+			}
+
+			log::info!(target: LOG_TARGET, "Indexed {} listings", indexed);
+			// Reading the listing and the region record, writing the index.
+			T::DbWeight::get().reads_writes(listings.saturating_mul(2), indexed)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				v4::Listings::<T>::iter_keys().all(|region_id| {
+					T::Regions::record(&region_id.into()).map_or(true, |record| {
+						ListingIndex::<T>::contains_key(Pallet::<T>::index_key(
+							region_id, record.end,
+						))
+					})
+				}),
+				"Listings are missing from the listing index"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrates the market storage from version 4 to 5.
+	pub type MigrateToV5<T> = VersionedMigration<
+		4,
+		5,
+		VersionUncheckedMigrateToV5<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v6 {
+	use super::*;
+
+	/// Moves the listing deposits from the market account to holds on the sellers.
+	///
+	/// If a deposit can't be moved it stays with the market account and the listing is treated
+	/// as holding no deposit.
+	pub struct VersionUncheckedMigrateToV6<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut listings = 0u64;
+			let mut moved = 0u64;
+			let market = Pallet::<T>::account_id();
+			v4::Listings::<T>::translate::<v4::Listing<T::AccountId, BalanceOf<T>, T::AssetId>, _>(
+				|region_id, mut listing| {
+					listings.saturating_inc();
+					if listing.deposit.is_zero() {
+						return Some(listing)
+					}
+
+					moved.saturating_inc();
+					let result = with_storage_layer::<_, DispatchError, _>(|| {
+						T::Currency::transfer(
+							&market,
+							&listing.seller,
+							listing.deposit,
+							Preservation::Expendable,
+						)?;
+						T::Currency::hold(
+							&HoldReason::ListingDeposit.into(),
+							&listing.seller,
+							listing.deposit,
+						)
+					});
+					if let Err(err) = result {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to hold the deposit of listing {:?}: {:?}",
+							region_id,
+							err
+						);
+						listing.deposit = Zero::zero();
+					}
+
+					Some(listing)
+				},
+			);
+
+			log::info!(target: LOG_TARGET, "Moved {} listing deposits to holds", moved);
+			// Reading and writing the listing, and the accounts and holds of moved deposits.
+			T::DbWeight::get().reads_writes(
+				listings.saturating_add(moved.saturating_mul(3)),
+				listings.saturating_add(moved.saturating_mul(3)),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, v4::Listings::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the market storage from version 5 to 6.
+	pub type MigrateToV6<T> = VersionedMigration<
+		5,
+		6,
+		VersionUncheckedMigrateToV6<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v7 {
+	use super::*;
+
+	/// Adds the minimum remaining duration to the listings. The migrated listings can be sold
+	/// until the last timeslice of the region.
+	pub struct VersionUncheckedMigrateToV7<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Listings::<T>::translate::<v4::Listing<T::AccountId, BalanceOf<T>, T::AssetId>, _>(
+				|_, old| {
+					migrated.saturating_inc();
+					Some(Listing {
+						seller: old.seller,
+						timeslice_price: old.timeslice_price,
+						asset: old.asset,
+						sale_recipients: old.sale_recipients,
+						expires_at: old.expires_at,
+						deposit: old.deposit,
+						allowed_buyers: old.allowed_buyers,
+						min_remaining_timeslices: 0,
+					})
+				},
+			);
+
+			log::info!(target: LOG_TARGET, "Migrated {} listings to v7", migrated);
+			T::DbWeight::get().reads_writes(migrated, migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(listing_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_listing_count(state, Listings::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the market storage from version 6 to 7.
+	pub type MigrateToV7<T> = VersionedMigration<
+		6,
+		7,
+		VersionUncheckedMigrateToV7<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
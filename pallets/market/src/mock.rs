@@ -13,7 +13,14 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-use frame_support::{pallet_prelude::*, parameter_types, traits::Everything};
+use crate::FeeHandler;
+use frame_support::{
+	pallet_prelude::*,
+	parameter_types,
+	traits::{tokens::ConversionFromAssetBalance, Contains, Everything},
+	PalletId,
+};
+use frame_system::EnsureRoot;
 use ismp::{
 	consensus::StateMachineId,
 	dispatcher::{DispatchRequest, FeeMetadata, IsmpDispatcher},
@@ -22,24 +29,35 @@ use ismp::{
 	router::PostResponse,
 };
 use ismp_testsuite::mocks::Host;
+use orml_currencies::BasicCurrencyAdapter;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
 use pallet_regions::primitives::StateMachineHeightProvider;
 use sp_core::{ConstU64, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, BlockNumberProvider, IdentityLookup},
-	BuildStorage,
+	BuildStorage, DispatchResult, Permill,
 };
 use std::sync::Arc;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub const TREASURY: u64 = 42;
+
+pub const NATIVE_ASSET_ID: u32 = 0;
+pub const RELAY_ASSET_ID: u32 = 1;
+/// An asset which is not supported by the market.
+pub const UNSUPPORTED_ASSET_ID: u32 = 2;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Balances: pallet_balances,
+		Tokens: orml_tokens,
+		Currencies: orml_currencies,
 		Regions: pallet_regions::{Pallet, Call, Storage, Event<T>},
-		Market: crate::{Pallet, Call, Storage, Event<T>},
+		Market: crate::{Pallet, Call, Storage, Event<T>, HoldReason},
 	}
 );
 
@@ -83,7 +101,7 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 	type MaxLocks = ();
-	type MaxHolds = ();
+	type MaxHolds = ConstU32<1>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -92,6 +110,37 @@ impl pallet_balances::Config for Test {
 	type MaxFreezes = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		1
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = ();
+}
+
+parameter_types! {
+	pub const NativeAssetId: u32 = NATIVE_ASSET_ID;
+}
+
+impl orml_currencies::Config for Test {
+	type MultiCurrency = Tokens;
+	type NativeCurrency = BasicCurrencyAdapter<Test, Balances, i64, u64>;
+	type GetNativeCurrencyId = NativeAssetId;
+	type WeightInfo = ();
+}
+
 pub struct MockStateMachineHeightProvider;
 impl StateMachineHeightProvider for MockStateMachineHeightProvider {
 	fn latest_state_machine_height(_id: StateMachineId) -> Option<u64> {
@@ -157,12 +206,69 @@ impl BlockNumberProvider for RelayBlockNumberProvider {
 	}
 }
 
+parameter_types! {
+	pub const MarketPalletId: PalletId = PalletId(*b"rgx/mrkt");
+	pub const TreasuryAccount: u64 = TREASURY;
+	pub const CleanupReward: Permill = Permill::from_percent(10);
+}
+
+pub struct MarketFeeHandler;
+impl FeeHandler<u64, u32, u64> for MarketFeeHandler {
+	fn handle(who: &u64, asset: u32, fee: u64) -> DispatchResult {
+		<Currencies as MultiCurrency<u64>>::transfer(asset, who, &TREASURY, fee)?;
+		Ok(())
+	}
+}
+
+pub struct SupportedAssets;
+impl Contains<u32> for SupportedAssets {
+	fn contains(asset: &u32) -> bool {
+		*asset == NATIVE_ASSET_ID || *asset == RELAY_ASSET_ID
+	}
+}
+
+/// One unit of the relay chain asset is worth two units of the native currency.
+pub struct AssetConverter;
+impl ConversionFromAssetBalance<u64, u32, u64> for AssetConverter {
+	type Error = ();
+
+	fn from_asset_balance(balance: u64, asset_id: u32) -> Result<u64, Self::Error> {
+		match asset_id {
+			NATIVE_ASSET_ID => Ok(balance),
+			RELAY_ASSET_ID => Ok(balance.saturating_mul(2)),
+			_ => Err(()),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_asset_id: u32) {}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type AssetId = u32;
+	type Assets = Currencies;
+	type SupportedAssets = SupportedAssets;
+	type NativeAssetId = NativeAssetId;
+	type AssetConverter = AssetConverter;
 	type Regions = Regions;
 	type RCBlockNumberProvider = RelayBlockNumberProvider;
 	type TimeslicePeriod = ConstU64<80>;
+	type FeeDestination = MarketFeeHandler;
+	type MarketFeeOrigin = EnsureRoot<u64>;
+	type PalletId = MarketPalletId;
+	type ListingDeposit = ConstU64<100>;
+	type TreasuryAccount = TreasuryAccount;
+	type CleanupReward = CleanupReward;
+	type MaxListings = ConstU32<2>;
+	type MaxBasketSize = ConstU32<3>;
+	type PriceHistoryLength = ConstU32<3>;
+	type MaxBuyOrders = ConstU32<3>;
+	type MaxAutoPurchases = ConstU32<1>;
+	type MaxSweepCandidates = ConstU32<10>;
+	type BulkPeriod = ConstU32<4>;
 	type WeightInfo = ();
 }
 
@@ -174,6 +280,15 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
+	orml_tokens::GenesisConfig::<Test> {
+		balances: vec![
+			(1, RELAY_ASSET_ID, 10_000_000),
+			(2, RELAY_ASSET_ID, 10_000_000),
+			(3, RELAY_ASSET_ID, 10_000_000),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
@@ -16,9 +16,12 @@
 use crate::{mock::*, *};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{nonfungible::Mutate, Get},
+	traits::{fungible::InspectHold, nonfungible::Mutate, Hooks},
+	weights::Weight,
+	BoundedVec,
 };
 use pallet_broker::{CoreMask, RegionRecord};
+use sp_core::bounded_vec;
 use sp_runtime::{DispatchError::Token, TokenError};
 
 #[test]
@@ -87,7 +90,13 @@ fn list_region_works() {
 		// Failure: Unknown region
 
 		assert_noop!(
-			Market::list_region(signer.clone(), region_id, price, None),
+			Market::list_region(
+				signer.clone(),
+				region_id,
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			),
 			Error::<Test>::RecordUnavailable
 		);
 
@@ -97,32 +106,68 @@ fn list_region_works() {
 		RelayBlockNumber::set(10 * timeslice);
 
 		assert_noop!(
-			Market::list_region(signer.clone(), region_id, price, None),
+			Market::list_region(
+				signer.clone(),
+				region_id,
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			),
 			Error::<Test>::RegionExpired
 		);
 
 		// Should be working
 		RelayBlockNumber::set(1 * timeslice);
-		assert_ok!(Market::list_region(signer.clone(), region_id, price, Some(recipient)));
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![(recipient, Permill::one())]),
+				..Default::default()
+			}
+		));
 
 		// Failure: Already listed
 		assert_noop!(
-			Market::list_region(signer, region_id, price, None),
+			Market::list_region(
+				signer,
+				region_id,
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			),
 			Error::<Test>::AlreadyListed
 		);
 
 		// Check storage items
 		assert_eq!(
 			Market::listings(region_id),
-			Some(Listing { seller, timeslice_price: price, sale_recipient: recipient })
+			Some(Listing {
+				seller,
+				timeslice_price: price,
+				asset: NATIVE_ASSET_ID,
+				sale_recipients: bounded_vec![(recipient, Permill::one())],
+				expires_at: None,
+				allowed_buyers: None,
+				deposit: 100,
+				min_remaining_timeslices: 0,
+			})
 		);
 
 		assert!(Regions::regions(region_id).unwrap().locked);
 
 		// Check events
 		System::assert_last_event(
-			Event::Listed { region_id, timeslice_price: price, seller, sale_recipient: recipient }
-				.into(),
+			Event::Listed {
+				region_id,
+				timeslice_price: price,
+				asset: NATIVE_ASSET_ID,
+				seller,
+				sale_recipients: bounded_vec![(recipient, Permill::one())],
+			}
+			.into(),
 		);
 	});
 }
@@ -144,10 +189,28 @@ fn unlist_region_works() {
 		// Failure: NotListed
 		assert_noop!(Market::unlist_region(signer.clone(), region_id), Error::<Test>::NotListed);
 
-		assert_ok!(Market::list_region(signer.clone(), region_id, price, Some(seller)));
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![(seller, Permill::one())]),
+				..Default::default()
+			}
+		));
 		assert_eq!(
 			Market::listings(region_id),
-			Some(Listing { seller, timeslice_price: price, sale_recipient: seller })
+			Some(Listing {
+				seller,
+				timeslice_price: price,
+				asset: NATIVE_ASSET_ID,
+				sale_recipients: bounded_vec![(seller, Permill::one())],
+				expires_at: None,
+				allowed_buyers: None,
+				deposit: 100,
+				min_remaining_timeslices: 0,
+			})
 		);
 
 		// Failure: NotAllowed
@@ -164,7 +227,9 @@ fn unlist_region_works() {
 		assert!(Regions::regions(region_id).unwrap().locked == false);
 
 		// Check events
-		System::assert_last_event(Event::Unlisted { region_id }.into())
+		System::assert_last_event(
+			Event::Unlisted { region_id, who: Some(seller), reason: UnlistReason::Seller }.into(),
+		)
 	});
 }
 
@@ -186,10 +251,28 @@ fn unlist_expired_region_works() {
 		// Failure: NotListed
 		assert_noop!(Market::unlist_region(signer.clone(), region_id), Error::<Test>::NotListed);
 
-		assert_ok!(Market::list_region(signer.clone(), region_id, price, Some(seller)));
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![(seller, Permill::one())]),
+				..Default::default()
+			}
+		));
 		assert_eq!(
 			Market::listings(region_id),
-			Some(Listing { seller, timeslice_price: price, sale_recipient: seller })
+			Some(Listing {
+				seller,
+				timeslice_price: price,
+				asset: NATIVE_ASSET_ID,
+				sale_recipients: bounded_vec![(seller, Permill::one())],
+				expires_at: None,
+				allowed_buyers: None,
+				deposit: 100,
+				min_remaining_timeslices: 0,
+			})
 		);
 
 		RelayBlockNumber::set(9 * timeslice);
@@ -198,7 +281,9 @@ fn unlist_expired_region_works() {
 		assert_ok!(Market::unlist_region(RuntimeOrigin::signed(3), region_id));
 
 		// Check events
-		System::assert_last_event(Event::Unlisted { region_id }.into());
+		System::assert_last_event(
+			Event::Unlisted { region_id, who: Some(3), reason: UnlistReason::Expired }.into(),
+		);
 
 		// Check storage items
 		assert!(Market::listings(region_id).is_none());
@@ -229,7 +314,16 @@ fn update_region_price_works() {
 			Error::<Test>::NotListed
 		);
 
-		assert_ok!(Market::list_region(signer.clone(), region_id, price, Some(recipient)));
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![(recipient, Permill::one())]),
+				..Default::default()
+			}
+		));
 
 		// Failure: NotAllowed - only the seller can update the price
 		assert_noop!(
@@ -254,7 +348,12 @@ fn update_region_price_works() {
 			Some(Listing {
 				seller,
 				timeslice_price: new_timeslice_price,
-				sale_recipient: recipient
+				asset: NATIVE_ASSET_ID,
+				sale_recipients: bounded_vec![(recipient, Permill::one())],
+				expires_at: None,
+				allowed_buyers: None,
+				deposit: 100,
+				min_remaining_timeslices: 0,
 			})
 		);
 
@@ -283,7 +382,12 @@ fn purchase_region_works() {
 
 		// Failure: NotListed
 		assert_noop!(
-			Market::purchase_region(RuntimeOrigin::signed(seller), region_id, 1 * timeslice_price),
+			Market::purchase_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				1 * timeslice_price,
+				None
+			),
 			Error::<Test>::NotListed
 		);
 
@@ -291,23 +395,37 @@ fn purchase_region_works() {
 			RuntimeOrigin::signed(seller),
 			region_id,
 			timeslice_price,
-			Some(recipient)
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![(recipient, Permill::one())]),
+				..Default::default()
+			}
 		));
 
 		// Failure: NotAllowed
 		assert_noop!(
-			Market::purchase_region(RuntimeOrigin::signed(seller), region_id, timeslice_price),
+			Market::purchase_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				timeslice_price,
+				None
+			),
 			Error::<Test>::NotAllowed
 		);
 		assert_noop!(
-			Market::purchase_region(RuntimeOrigin::signed(recipient), region_id, timeslice_price),
+			Market::purchase_region(
+				RuntimeOrigin::signed(recipient),
+				region_id,
+				timeslice_price,
+				None
+			),
 			Error::<Test>::NotAllowed
 		);
 
 		// Failure: PriceTooHigh
 		RelayBlockNumber::set(timeslice);
 		assert_noop!(
-			Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, timeslice_price),
+			Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, timeslice_price, None),
 			Error::<Test>::PriceTooHigh
 		);
 
@@ -319,7 +437,12 @@ fn purchase_region_works() {
 			balance_buyer_old.saturating_sub(3 * timeslice_price),
 		));
 		assert_noop!(
-			Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, 8 * timeslice_price),
+			Market::purchase_region(
+				RuntimeOrigin::signed(buyer),
+				region_id,
+				8 * timeslice_price,
+				None
+			),
 			Token(TokenError::FundsUnavailable)
 		);
 		assert_ok!(Balances::transfer_keep_alive(
@@ -338,7 +461,8 @@ fn purchase_region_works() {
 		assert_ok!(Market::purchase_region(
 			RuntimeOrigin::signed(buyer),
 			region_id,
-			5 * timeslice_price
+			5 * timeslice_price,
+			None
 		));
 
 		// Check storage items
@@ -346,7 +470,23 @@ fn purchase_region_works() {
 		assert!(Regions::regions(region_id).unwrap().locked == false);
 
 		// Check events
-		System::assert_last_event(Event::Purchased { region_id, buyer, total_price: price }.into());
+		System::assert_has_event(
+			Event::Unlisted { region_id, who: Some(buyer), reason: UnlistReason::Purchased }.into(),
+		);
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![(recipient, price)],
+				asset: NATIVE_ASSET_ID,
+				total_price: price,
+				timeslice_price,
+				remaining_duration: 4,
+				fee: 0,
+			}
+			.into(),
+		);
 
 		// Check account balances
 		let balance_recipient = Balances::free_balance(recipient);
@@ -356,3 +496,1823 @@ fn purchase_region_works() {
 		assert_eq!(balance_buyer.saturating_add(price), balance_buyer_old);
 	});
 }
+
+#[test]
+fn purchase_region_splits_proceeds() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+		let partner = 4;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		let record: RegionRecordOf<Test> = RegionRecord { end: 8, owner: 1, paid: None };
+		assert_ok!(Regions::set_record(region_id, record));
+
+		let timeslice_price = 1_000_000;
+
+		// Failure: InvalidSaleRecipients
+		assert_noop!(
+			Market::list_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				timeslice_price,
+				NATIVE_ASSET_ID,
+				ListingOptions {
+					sale_recipients: Some(bounded_vec![
+						(seller, Permill::from_percent(60)),
+						(partner, Permill::from_percent(30))
+					]),
+					..Default::default()
+				}
+			),
+			Error::<Test>::InvalidSaleRecipients
+		);
+		assert_noop!(
+			Market::list_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				timeslice_price,
+				NATIVE_ASSET_ID,
+				ListingOptions { sale_recipients: Some(Default::default()), ..Default::default() }
+			),
+			Error::<Test>::InvalidSaleRecipients
+		);
+
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			timeslice_price,
+			NATIVE_ASSET_ID,
+			ListingOptions {
+				sale_recipients: Some(bounded_vec![
+					(seller, Permill::from_percent(70)),
+					(partner, Permill::from_percent(30))
+				]),
+				..Default::default()
+			}
+		));
+
+		// Failure: NotAllowed
+		assert_noop!(
+			Market::purchase_region(RuntimeOrigin::signed(partner), region_id, 0, None),
+			Error::<Test>::NotAllowed
+		);
+
+		assert_ok!(Market::set_market_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+
+		let balance_seller_old = Balances::free_balance(seller);
+		let price = 8 * timeslice_price;
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, price, None));
+
+		// The fee is deducted first, the rest is split among the recipients:
+		let revenue = price - price / 10;
+		// The seller also gets back the listing deposit:
+		assert_eq!(Balances::free_balance(seller), balance_seller_old + revenue * 7 / 10 + 100);
+		assert_eq!(Balances::free_balance(partner), revenue * 3 / 10);
+
+		// Each recipient's part of the revenue is reported separately:
+		System::assert_has_event(
+			Event::RegionSoldPartially {
+				region_id,
+				sale_recipient: seller,
+				share: Permill::from_percent(70),
+				amount: revenue * 7 / 10,
+			}
+			.into(),
+		);
+		System::assert_has_event(
+			Event::RegionSoldPartially {
+				region_id,
+				sale_recipient: partner,
+				share: Permill::from_percent(30),
+				amount: revenue * 3 / 10,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![
+					(seller, revenue * 7 / 10),
+					(partner, revenue * 3 / 10)
+				],
+				asset: NATIVE_ASSET_ID,
+				total_price: price,
+				timeslice_price,
+				remaining_duration: 8,
+				fee: price / 10,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sale_proceeds_split_rounds_down() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 2, owner: 1, paid: None }));
+
+		// The shares don't split the price evenly:
+		let recipients: SaleRecipients<u64> = bounded_vec![
+			(seller, Permill::from_parts(250_001)),
+			(1, Permill::from_parts(250_001)),
+			(4, Permill::from_parts(250_001)),
+			(5, Permill::from_parts(249_997)),
+		];
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			1, // timeslice price
+			NATIVE_ASSET_ID,
+			ListingOptions { sale_recipients: Some(recipients), ..Default::default() }
+		));
+
+		let balance_buyer_old = Balances::free_balance(buyer);
+		let balance_seller_old = Balances::free_balance(seller);
+		let balance_1_old = Balances::free_balance(1);
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, 2, None));
+
+		// The buyer pays exactly the price, the remainder goes to the last recipient:
+		assert_eq!(Balances::free_balance(buyer), balance_buyer_old - 2);
+		// The seller only gets back the listing deposit:
+		assert_eq!(Balances::free_balance(seller), balance_seller_old + 100);
+		assert_eq!(Balances::free_balance(1), balance_1_old);
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(Balances::free_balance(5), 2);
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![(seller, 0), (1, 0), (4, 0), (5, 2)],
+				asset: NATIVE_ASSET_ID,
+				total_price: 2,
+				timeslice_price: 1,
+				remaining_duration: 2,
+				fee: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_market_fee_works() {
+	new_test_ext().execute_with(|| {
+		let fee = Permill::from_percent(10);
+
+		// Failure: BadOrigin
+		assert_noop!(
+			Market::set_market_fee(RuntimeOrigin::signed(1), fee),
+			DispatchError::BadOrigin
+		);
+
+		// Should be working
+		assert_ok!(Market::set_market_fee(RuntimeOrigin::root(), fee));
+
+		// Check storage items
+		assert_eq!(Market::market_fee(), fee);
+
+		// Check events
+		System::assert_last_event(Event::MarketFeeUpdated { fee }.into());
+	});
+}
+
+#[test]
+fn purchase_region_charges_market_fee() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+
+		let record: RegionRecordOf<Test> = RegionRecord { end: 8, owner: 1, paid: None };
+		let timeslice_price = 1_000_000;
+
+		assert_ok!(Regions::set_record(region_id, record.clone()));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			timeslice_price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		assert_ok!(Market::set_market_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+
+		let balance_seller_old = Balances::free_balance(seller);
+		let balance_buyer_old = Balances::free_balance(buyer);
+
+		let price = 8 * timeslice_price;
+		let fee = price / 10;
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, price, None));
+
+		// Check events
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![(seller, price - fee)],
+				asset: NATIVE_ASSET_ID,
+				total_price: price,
+				timeslice_price,
+				remaining_duration: 8,
+				fee,
+			}
+			.into(),
+		);
+
+		// The buyer pays the full price, the fee goes to the treasury:
+		assert_eq!(Balances::free_balance(buyer), balance_buyer_old - price);
+		// The seller also gets back the listing deposit:
+		assert_eq!(Balances::free_balance(seller), balance_seller_old + price - fee + 100);
+		assert_eq!(Balances::free_balance(TREASURY), fee);
+	});
+}
+
+#[test]
+fn private_listing_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		let record: RegionRecordOf<Test> = RegionRecord { end: 8, owner: 1, paid: None };
+		assert_ok!(Regions::set_record(region_id, record));
+
+		let timeslice_price = 1_000_000;
+		let allowed_buyers: AllowedBuyers<u64> = vec![buyer].try_into().unwrap();
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			timeslice_price,
+			NATIVE_ASSET_ID,
+			ListingOptions { allowed_buyers: Some(allowed_buyers.clone()), ..Default::default() }
+		));
+		assert_eq!(Market::listings(region_id).unwrap().allowed_buyers, Some(allowed_buyers));
+
+		let price = 8 * timeslice_price;
+		// Only the designated buyer can purchase the region:
+		assert_noop!(
+			Market::purchase_region(RuntimeOrigin::signed(1), region_id, price, None),
+			Error::<Test>::NotAllowed
+		);
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, price, None));
+		assert_eq!(Regions::regions(region_id).unwrap().owner, buyer);
+
+		// An empty allow-list makes the listing public:
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(buyer),
+			region_id,
+			timeslice_price,
+			NATIVE_ASSET_ID,
+			ListingOptions { allowed_buyers: Some(Default::default()), ..Default::default() }
+		));
+		assert_eq!(Market::listings(region_id).unwrap().allowed_buyers, None);
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(1), region_id, price, None));
+	});
+}
+
+#[test]
+fn listing_deposit_and_limit_works() {
+	new_test_ext().execute_with(|| {
+		let seller = 2;
+		let signer = RuntimeOrigin::signed(seller);
+		let deposit: u64 = <Test as crate::Config>::ListingDeposit::get();
+		let price = 1_000_000;
+
+		let regions: Vec<RegionId> = (0..3)
+			.map(|core| RegionId { begin: 0, core, mask: CoreMask::complete() })
+			.collect();
+		for region_id in regions.iter() {
+			assert_ok!(Regions::mint_into(&(*region_id).into(), &seller));
+			assert_ok!(Regions::set_record(
+				*region_id,
+				RegionRecord { end: 8, owner: seller, paid: None }
+			));
+		}
+
+		let balance_seller_old = Balances::free_balance(seller);
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			regions[0],
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			regions[1],
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		// The deposits are held by the market:
+		let hold_reason = HoldReason::ListingDeposit.into();
+		assert_eq!(Balances::free_balance(seller), balance_seller_old - 2 * deposit);
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &seller), 2 * deposit);
+		assert_eq!(Market::seller_listings(seller).to_vec(), vec![regions[0], regions[1]]);
+
+		// Failure: TooManyListings
+		assert_noop!(
+			Market::list_region(
+				signer.clone(),
+				regions[2],
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			),
+			Error::<Test>::TooManyListings
+		);
+
+		// Unlisting returns the deposit and frees up a slot:
+		assert_ok!(Market::unlist_region(signer.clone(), regions[0]));
+		assert_eq!(Balances::free_balance(seller), balance_seller_old - deposit);
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &seller), deposit);
+		assert_eq!(Market::seller_listings(seller).to_vec(), vec![regions[1]]);
+
+		assert_ok!(Market::list_region(
+			signer,
+			regions[2],
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_eq!(Market::seller_listings(seller).to_vec(), vec![regions[1], regions[2]]);
+	});
+}
+
+#[test]
+fn listing_expiry_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+		let signer = RuntimeOrigin::signed(seller);
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let price = 1_000_000;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(
+			region_id,
+			RegionRecord { end: 8, owner: seller, paid: None }
+		));
+
+		// Failure: InvalidExpiry
+		RelayBlockNumber::set(2 * timeslice);
+		assert_noop!(
+			Market::list_region(
+				signer.clone(),
+				region_id,
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions { expires_at: Some(2), ..Default::default() }
+			),
+			Error::<Test>::InvalidExpiry
+		);
+
+		assert_ok!(Market::list_region(
+			signer,
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions { expires_at: Some(4), ..Default::default() }
+		));
+
+		// Only the seller can unlist before the listing expires:
+		assert_noop!(
+			Market::unlist_region(RuntimeOrigin::signed(buyer), region_id),
+			Error::<Test>::NotAllowed
+		);
+
+		// Failure: ListingExpired
+		RelayBlockNumber::set(4 * timeslice);
+		assert_noop!(
+			Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, 8 * price, None),
+			Error::<Test>::ListingExpired
+		);
+
+		// Anyone can unlist an expired listing:
+		let balance_seller_old = Balances::total_balance(&seller);
+		let balance_buyer_old = Balances::free_balance(buyer);
+		assert_ok!(Market::unlist_region(RuntimeOrigin::signed(buyer), region_id));
+		assert!(Market::listings(region_id).is_none());
+		assert!(Regions::regions(region_id).unwrap().locked == false);
+
+		// The deposit is slashed, the caller gets rewarded with a part of it:
+		System::assert_has_event(
+			Event::ListingDepositSlashed { region_id, seller, amount: 90, reward: 10 }.into(),
+		);
+		assert_eq!(Balances::total_balance(&seller), balance_seller_old - 100);
+		assert_eq!(Balances::free_balance(buyer), balance_buyer_old + 10);
+		assert_eq!(Balances::free_balance(TREASURY), 90);
+	});
+}
+
+#[test]
+fn min_remaining_timeslices_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+		let signer = RuntimeOrigin::signed(seller);
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let price = 1_000_000;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(
+			region_id,
+			RegionRecord { end: 8, owner: seller, paid: None }
+		));
+
+		// Failure: ListingStale
+		RelayBlockNumber::set(5 * timeslice);
+		assert_noop!(
+			Market::list_region(
+				signer.clone(),
+				region_id,
+				price,
+				NATIVE_ASSET_ID,
+				ListingOptions { min_remaining_timeslices: 4, ..Default::default() }
+			),
+			Error::<Test>::ListingStale
+		);
+
+		RelayBlockNumber::set(2 * timeslice);
+		assert_ok!(Market::list_region(
+			signer,
+			region_id,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions { min_remaining_timeslices: 4, ..Default::default() }
+		));
+		assert_eq!(Market::quote(region_id, None), Some((NATIVE_ASSET_ID, 6 * price)));
+
+		// The region can be bought as long as enough timeslices remain:
+		RelayBlockNumber::set(4 * timeslice);
+		assert_eq!(Market::quote(region_id, None), Some((NATIVE_ASSET_ID, 4 * price)));
+		assert_noop!(
+			Market::unlist_region(RuntimeOrigin::signed(buyer), region_id),
+			Error::<Test>::NotAllowed
+		);
+
+		// Once fewer timeslices remain the listing is stale:
+		RelayBlockNumber::set(5 * timeslice);
+		assert_eq!(Market::quote(region_id, None), None);
+		assert_noop!(
+			Market::purchase_region(RuntimeOrigin::signed(buyer), region_id, 8 * price, None),
+			Error::<Test>::ListingStale
+		);
+
+		// Stale listings are treated as expired, so anyone can remove them:
+		assert_ok!(Market::unlist_region(RuntimeOrigin::signed(buyer), region_id));
+		System::assert_last_event(
+			Event::Unlisted { region_id, who: Some(buyer), reason: UnlistReason::Stale }.into(),
+		);
+		assert!(Market::listings(region_id).is_none());
+		assert!(Regions::regions(region_id).unwrap().locked == false);
+	});
+}
+
+#[test]
+fn expired_listings_are_swept_on_idle() {
+	new_test_ext().execute_with(|| {
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let price = 1_000_000;
+
+		// Region with an expiring listing:
+		let expiring = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&expiring.into(), &1));
+		assert_ok!(Regions::set_record(expiring, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(1),
+			expiring,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions { expires_at: Some(2), ..Default::default() }
+		));
+
+		// Region which expires itself:
+		let short = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&short.into(), &2));
+		assert_ok!(Regions::set_record(short, RegionRecord { end: 3, owner: 2, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(2),
+			short,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		// Region which stays valid:
+		let valid = RegionId { begin: 0, core: 2, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&valid.into(), &3));
+		assert_ok!(Regions::set_record(valid, RegionRecord { end: 8, owner: 3, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(3),
+			valid,
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		// Nothing expired yet:
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::listings(expiring).is_some());
+		assert!(Market::listings(short).is_some());
+
+		// Without enough weight nothing happens:
+		RelayBlockNumber::set(4 * timeslice);
+		assert_eq!(Market::on_idle(1, Weight::zero()), Weight::zero());
+		assert!(Market::listings(expiring).is_some());
+
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::listings(expiring).is_none());
+		assert!(Market::listings(short).is_none());
+		assert!(Market::listings(valid).is_some());
+		System::assert_has_event(
+			Event::Unlisted { region_id: expiring, who: None, reason: UnlistReason::Expired }
+				.into(),
+		);
+
+		assert!(Regions::regions(expiring).unwrap().locked == false);
+		assert!(Regions::regions(short).unwrap().locked == false);
+		assert!(Market::seller_listings(1).is_empty());
+		assert!(Market::seller_listings(2).is_empty());
+
+		// The deposits of the expired listings got returned to the sellers:
+		assert_eq!(Balances::free_balance(TREASURY), 0);
+		let hold_reason = HoldReason::ListingDeposit.into();
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &1), 0);
+		assert_eq!(Balances::free_balance(1), 10_000_000);
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &2), 0);
+		assert_eq!(Balances::free_balance(2), 10_000_000);
+		assert_eq!(Balances::balance_on_hold(&hold_reason, &3), 100);
+	});
+}
+
+#[test]
+fn stale_listings_are_swept_on_idle() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(
+			region_id,
+			RegionRecord { end: 8, owner: seller, paid: None }
+		));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			1_000_000,
+			NATIVE_ASSET_ID,
+			ListingOptions { min_remaining_timeslices: 4, ..Default::default() }
+		));
+
+		// Enough timeslices remain:
+		RelayBlockNumber::set(4 * timeslice);
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::listings(region_id).is_some());
+
+		// Once the listing goes stale it gets unlisted:
+		RelayBlockNumber::set(5 * timeslice);
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::listings(region_id).is_none());
+		System::assert_last_event(
+			Event::Unlisted { region_id, who: None, reason: UnlistReason::Stale }.into(),
+		);
+		assert!(Regions::regions(region_id).unwrap().locked == false);
+		assert_eq!(Balances::free_balance(seller), 10_000_000);
+	});
+}
+
+#[test]
+fn list_region_in_other_assets_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let signer = RuntimeOrigin::signed(seller);
+		let price = 1_000_000;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Failure: UnsupportedAsset
+		assert_noop!(
+			Market::list_region(
+				signer.clone(),
+				region_id,
+				price,
+				UNSUPPORTED_ASSET_ID,
+				ListingOptions::default()
+			),
+			Error::<Test>::UnsupportedAsset
+		);
+
+		assert_ok!(Market::list_region(
+			signer,
+			region_id,
+			price,
+			RELAY_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_eq!(Market::listings(region_id).unwrap().asset, RELAY_ASSET_ID);
+
+		System::assert_last_event(
+			Event::Listed {
+				region_id,
+				timeslice_price: price,
+				asset: RELAY_ASSET_ID,
+				seller,
+				sale_recipients: bounded_vec![(seller, Permill::one())],
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn purchase_region_in_listing_asset_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+		let timeslice_price = 1_000;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			timeslice_price,
+			RELAY_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		let native_balance_buyer_old = Balances::free_balance(buyer);
+		let price = 8 * timeslice_price;
+
+		// Failure: UnsupportedConversion
+		assert_noop!(
+			Market::purchase_region(
+				RuntimeOrigin::signed(buyer),
+				region_id,
+				price,
+				Some(UNSUPPORTED_ASSET_ID)
+			),
+			Error::<Test>::UnsupportedConversion
+		);
+
+		assert_ok!(Market::purchase_region(
+			RuntimeOrigin::signed(buyer),
+			region_id,
+			price,
+			Some(RELAY_ASSET_ID)
+		));
+
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![(seller, price)],
+				asset: RELAY_ASSET_ID,
+				total_price: price,
+				timeslice_price,
+				remaining_duration: 8,
+				fee: 0,
+			}
+			.into(),
+		);
+
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &buyer), 10_000_000 - price);
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &seller), 10_000_000 + price);
+		// The native balance of the buyer is untouched:
+		assert_eq!(Balances::free_balance(buyer), native_balance_buyer_old);
+		assert_eq!(Regions::regions(region_id).unwrap().owner, buyer);
+	});
+}
+
+#[test]
+fn cross_asset_purchase_works() {
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		let buyer = 3;
+		let timeslice_price = 1_000;
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			region_id,
+			timeslice_price,
+			RELAY_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_ok!(Market::set_market_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+
+		let balance_buyer_old = Balances::free_balance(buyer);
+		let balance_seller_old = Balances::free_balance(seller);
+
+		// One unit of the relay asset is worth two native units in the mock.
+		let price = 2 * 8 * timeslice_price;
+		let fee = price / 10;
+
+		// Failure: PriceTooHigh, `max_price` is denominated in the native currency.
+		assert_noop!(
+			Market::purchase_region(
+				RuntimeOrigin::signed(buyer),
+				region_id,
+				8 * timeslice_price,
+				Some(NATIVE_ASSET_ID)
+			),
+			Error::<Test>::PriceTooHigh
+		);
+
+		assert_ok!(Market::purchase_region(
+			RuntimeOrigin::signed(buyer),
+			region_id,
+			price,
+			Some(NATIVE_ASSET_ID)
+		));
+
+		System::assert_last_event(
+			Event::Purchased {
+				region_id,
+				buyer,
+				seller,
+				sale_recipients: bounded_vec![(seller, price - fee)],
+				asset: NATIVE_ASSET_ID,
+				total_price: price,
+				timeslice_price,
+				remaining_duration: 8,
+				fee,
+			}
+			.into(),
+		);
+
+		assert_eq!(Balances::free_balance(buyer), balance_buyer_old - price);
+		// The seller also gets back the listing deposit:
+		assert_eq!(Balances::free_balance(seller), balance_seller_old + price - fee + 100);
+		assert_eq!(Balances::free_balance(TREASURY), fee);
+		// No relay chain assets were spent:
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &buyer), 10_000_000);
+		assert_eq!(Regions::regions(region_id).unwrap().owner, buyer);
+	});
+}
+
+#[test]
+fn purchase_many_works() {
+	new_test_ext().execute_with(|| {
+		let seller = 2;
+		let buyer = 3;
+		let timeslice_price = 1_000;
+		let regions: Vec<RegionId> = (0..2)
+			.map(|core| RegionId { begin: 0, core, mask: CoreMask::complete() })
+			.collect();
+
+		for region_id in regions.iter() {
+			assert_ok!(Regions::mint_into(&(*region_id).into(), &seller));
+			assert_ok!(Regions::set_record(
+				*region_id,
+				RegionRecord { end: 8, owner: 1, paid: None }
+			));
+			assert_ok!(Market::list_region(
+				RuntimeOrigin::signed(seller),
+				*region_id,
+				timeslice_price,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			));
+		}
+
+		let price = 8 * timeslice_price;
+		let purchases: BoundedVec<_, _> = regions
+			.iter()
+			.map(|region_id| (*region_id, price))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		// Failure: EmptyBasket
+		assert_noop!(
+			Market::purchase_many(RuntimeOrigin::signed(buyer), Default::default(), price, None),
+			Error::<Test>::EmptyBasket
+		);
+
+		// Failure: PriceTooHigh, each region is within its own limit but the combined price is
+		// too high. None of the regions are purchased.
+		assert_noop!(
+			Market::purchase_many(RuntimeOrigin::signed(buyer), purchases.clone(), price, None),
+			Error::<Test>::PriceTooHigh
+		);
+		assert!(Market::listings(regions[0]).is_some());
+		assert_eq!(Regions::regions(regions[0]).unwrap().owner, seller);
+
+		// Failure: PriceTooHigh, the price of a single region is too high.
+		let too_cheap: BoundedVec<_, _> =
+			vec![(regions[0], price), (regions[1], price - 1)].try_into().unwrap();
+		assert_noop!(
+			Market::purchase_many(RuntimeOrigin::signed(buyer), too_cheap, 2 * price, None),
+			Error::<Test>::PriceTooHigh
+		);
+
+		let balance_buyer_old = Balances::free_balance(buyer);
+		assert_ok!(Market::purchase_many(RuntimeOrigin::signed(buyer), purchases, 2 * price, None));
+
+		for region_id in regions {
+			assert!(Market::listings(region_id).is_none());
+			assert_eq!(Regions::regions(region_id).unwrap().owner, buyer);
+		}
+		assert_eq!(Balances::free_balance(buyer), balance_buyer_old - 2 * price);
+	});
+}
+
+#[test]
+fn purchase_many_requires_a_single_asset() {
+	new_test_ext().execute_with(|| {
+		let seller = 2;
+		let buyer = 3;
+		let timeslice_price = 1_000;
+		let native_region = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let relay_region = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+
+		for (region_id, asset) in [(native_region, NATIVE_ASSET_ID), (relay_region, RELAY_ASSET_ID)]
+		{
+			assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+			assert_ok!(Regions::set_record(
+				region_id,
+				RegionRecord { end: 8, owner: 1, paid: None }
+			));
+			assert_ok!(Market::list_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				timeslice_price,
+				asset,
+				ListingOptions::default()
+			));
+		}
+
+		let price = 8 * timeslice_price;
+		let purchases: BoundedVec<_, _> =
+			vec![(native_region, 2 * price), (relay_region, 2 * price)].try_into().unwrap();
+
+		// Failure: MixedAssets
+		assert_noop!(
+			Market::purchase_many(RuntimeOrigin::signed(buyer), purchases.clone(), 4 * price, None),
+			Error::<Test>::MixedAssets
+		);
+
+		// Paying everything in the native currency works. The relay asset listing costs twice as
+		// much when converted.
+		assert_ok!(Market::purchase_many(
+			RuntimeOrigin::signed(buyer),
+			purchases,
+			3 * price,
+			Some(NATIVE_ASSET_ID)
+		));
+		assert_eq!(Regions::regions(native_region).unwrap().owner, buyer);
+		assert_eq!(Regions::regions(relay_region).unwrap().owner, buyer);
+	});
+}
+
+#[test]
+fn propose_swap_works() {
+	new_test_ext().execute_with(|| {
+		let proposer = 2;
+		let counterparty = 3;
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		let top_up = Some((NATIVE_ASSET_ID, 1_000));
+
+		assert_ok!(Regions::mint_into(&offered.into(), &proposer));
+		assert_ok!(Regions::mint_into(&requested.into(), &counterparty));
+
+		// Failure: RecordUnavailable
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(proposer), offered, requested, top_up, 4),
+			Error::<Test>::RecordUnavailable
+		);
+		assert_ok!(Regions::set_record(offered, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Failure: InvalidSwap
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(proposer), offered, offered, top_up, 4),
+			Error::<Test>::InvalidSwap
+		);
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(counterparty), offered, requested, None, 4),
+			Error::<Test>::InvalidSwap
+		);
+
+		// Failure: InvalidExpiry
+		RelayBlockNumber::set(2 * 80);
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(proposer), offered, requested, top_up, 2),
+			Error::<Test>::InvalidExpiry
+		);
+
+		// Failure: UnsupportedAsset
+		assert_noop!(
+			Market::propose_swap(
+				RuntimeOrigin::signed(proposer),
+				offered,
+				requested,
+				Some((UNSUPPORTED_ASSET_ID, 1_000)),
+				4
+			),
+			Error::<Test>::UnsupportedAsset
+		);
+
+		// Failure: NotOwner
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(1), offered, requested, top_up, 4),
+			pallet_regions::Error::<Test>::NotOwner
+		);
+
+		let balance_proposer_old = Balances::free_balance(proposer);
+		assert_ok!(Market::propose_swap(
+			RuntimeOrigin::signed(proposer),
+			offered,
+			requested,
+			top_up,
+			4
+		));
+
+		// Check storage items
+		assert_eq!(
+			Market::swaps(offered),
+			Some(Swap { proposer, requested, top_up, expires_at: 4 })
+		);
+		assert!(Regions::regions(offered).unwrap().locked);
+		assert!(Regions::regions(requested).unwrap().locked);
+		assert_eq!(Balances::free_balance(proposer), balance_proposer_old - 1_000);
+		assert_eq!(Balances::free_balance(Market::account_id()), 1_000);
+
+		// Check events
+		System::assert_last_event(
+			Event::SwapProposed { offered, requested, proposer, top_up, expires_at: 4 }.into(),
+		);
+
+		// Failure: RegionLocked
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(proposer), offered, requested, None, 4),
+			pallet_regions::Error::<Test>::RegionLocked
+		);
+		// The requested region is locked as well:
+		let other = RegionId { begin: 0, core: 2, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&other.into(), &proposer));
+		assert_ok!(Regions::set_record(other, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_noop!(
+			Market::propose_swap(RuntimeOrigin::signed(proposer), other, requested, None, 4),
+			pallet_regions::Error::<Test>::RegionLocked
+		);
+	});
+}
+
+#[test]
+fn accept_swap_works() {
+	new_test_ext().execute_with(|| {
+		let proposer = 2;
+		let counterparty = 3;
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+
+		assert_ok!(Regions::mint_into(&offered.into(), &proposer));
+		assert_ok!(Regions::set_record(offered, RegionRecord { end: 5, owner: 1, paid: None }));
+		assert_ok!(Regions::mint_into(&requested.into(), &counterparty));
+		assert_ok!(Regions::set_record(requested, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Failure: UnknownSwap
+		assert_noop!(
+			Market::accept_swap(RuntimeOrigin::signed(counterparty), offered),
+			Error::<Test>::UnknownSwap
+		);
+
+		assert_ok!(Market::propose_swap(
+			RuntimeOrigin::signed(proposer),
+			offered,
+			requested,
+			Some((RELAY_ASSET_ID, 1_000)),
+			6
+		));
+
+		// Failure: NotAllowed, only the owner of the requested region can accept.
+		assert_noop!(
+			Market::accept_swap(RuntimeOrigin::signed(1), offered),
+			Error::<Test>::NotAllowed
+		);
+
+		// The requested region is locked until the proposal is resolved:
+		assert_noop!(
+			Market::list_region(
+				RuntimeOrigin::signed(counterparty),
+				requested,
+				1_000,
+				NATIVE_ASSET_ID,
+				ListingOptions::default()
+			),
+			Error::<Test>::RegionLocked
+		);
+
+		// Failure: RegionExpired, the offered region expired before the proposal.
+		RelayBlockNumber::set(5 * 80);
+		assert_noop!(
+			Market::accept_swap(RuntimeOrigin::signed(counterparty), offered),
+			Error::<Test>::RegionExpired
+		);
+
+		// Failure: SwapExpired
+		RelayBlockNumber::set(6 * 80);
+		assert_noop!(
+			Market::accept_swap(RuntimeOrigin::signed(counterparty), offered),
+			Error::<Test>::SwapExpired
+		);
+
+		RelayBlockNumber::set(3 * 80);
+		assert_ok!(Market::accept_swap(RuntimeOrigin::signed(counterparty), offered));
+
+		// Check storage items
+		assert!(Market::swaps(offered).is_none());
+		let offered_region = Regions::regions(offered).unwrap();
+		assert_eq!((offered_region.owner, offered_region.locked), (counterparty, false));
+		let requested_region = Regions::regions(requested).unwrap();
+		assert_eq!((requested_region.owner, requested_region.locked), (proposer, false));
+
+		// The top-up is paid to the counterparty:
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &proposer), 10_000_000 - 1_000);
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &counterparty), 10_000_000 + 1_000);
+
+		// Check events
+		System::assert_last_event(
+			Event::SwapAccepted { offered, requested, proposer, counterparty }.into(),
+		);
+	});
+}
+
+#[test]
+fn cancel_swap_works() {
+	new_test_ext().execute_with(|| {
+		let proposer = 2;
+		let counterparty = 3;
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+
+		assert_ok!(Regions::mint_into(&offered.into(), &proposer));
+		assert_ok!(Regions::set_record(offered, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_ok!(Regions::mint_into(&requested.into(), &counterparty));
+
+		// Failure: UnknownSwap
+		assert_noop!(
+			Market::cancel_swap(RuntimeOrigin::signed(proposer), offered),
+			Error::<Test>::UnknownSwap
+		);
+
+		assert_ok!(Market::propose_swap(
+			RuntimeOrigin::signed(proposer),
+			offered,
+			requested,
+			Some((RELAY_ASSET_ID, 1_000)),
+			4
+		));
+
+		// Failure: NotAllowed, only the proposer and the owner of the requested region can cancel
+		// before the proposal expires.
+		assert_noop!(
+			Market::cancel_swap(RuntimeOrigin::signed(1), offered),
+			Error::<Test>::NotAllowed
+		);
+
+		// The owner of the requested region can reject the proposal:
+		assert_ok!(Market::cancel_swap(RuntimeOrigin::signed(counterparty), offered));
+
+		// Check storage items
+		assert!(Market::swaps(offered).is_none());
+		assert!(!Regions::regions(offered).unwrap().locked);
+		assert!(!Regions::regions(requested).unwrap().locked);
+		assert_eq!(Regions::regions(offered).unwrap().owner, proposer);
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &proposer), 10_000_000);
+
+		// Check events
+		System::assert_last_event(Event::SwapCancelled { offered }.into());
+
+		// Anyone can cancel an expired proposal:
+		assert_ok!(Market::propose_swap(
+			RuntimeOrigin::signed(proposer),
+			offered,
+			requested,
+			Some((RELAY_ASSET_ID, 1_000)),
+			4
+		));
+		RelayBlockNumber::set(4 * 80);
+		assert_ok!(Market::cancel_swap(RuntimeOrigin::signed(1), offered));
+		assert!(Market::swaps(offered).is_none());
+		assert!(!Regions::regions(requested).unwrap().locked);
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &proposer), 10_000_000);
+	});
+}
+
+#[test]
+fn expired_swaps_are_swept_on_idle() {
+	new_test_ext().execute_with(|| {
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let record = RegionRecord { end: 8, owner: 1, paid: None };
+
+		// Swap for a region which expires before the proposal:
+		let offered = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let short = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&offered.into(), &2));
+		assert_ok!(Regions::set_record(offered, record.clone()));
+		assert_ok!(Regions::mint_into(&short.into(), &3));
+		assert_ok!(Regions::set_record(short, RegionRecord { end: 3, owner: 3, paid: None }));
+		assert_ok!(Market::propose_swap(
+			RuntimeOrigin::signed(2),
+			offered,
+			short,
+			Some((RELAY_ASSET_ID, 1_000)),
+			6
+		));
+
+		// Swap proposal which expires itself:
+		let expiring = RegionId { begin: 0, core: 2, mask: CoreMask::complete() };
+		let requested = RegionId { begin: 0, core: 3, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&expiring.into(), &2));
+		assert_ok!(Regions::set_record(expiring, record.clone()));
+		assert_ok!(Regions::mint_into(&requested.into(), &3));
+		assert_ok!(Regions::set_record(requested, record.clone()));
+		assert_ok!(Market::propose_swap(RuntimeOrigin::signed(2), expiring, requested, None, 4));
+
+		// Swap proposal which stays valid:
+		let valid = RegionId { begin: 0, core: 4, mask: CoreMask::complete() };
+		let other = RegionId { begin: 0, core: 5, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&valid.into(), &1));
+		assert_ok!(Regions::set_record(valid, record.clone()));
+		assert_ok!(Regions::mint_into(&other.into(), &3));
+		assert_ok!(Regions::set_record(other, record));
+		assert_ok!(Market::propose_swap(RuntimeOrigin::signed(1), valid, other, None, 8));
+
+		// Nothing expired yet:
+		Market::on_idle(1, Weight::MAX);
+		assert_eq!(Swaps::<Test>::iter().count(), 3);
+
+		// The requested region expired, so the swap can no longer be accepted:
+		RelayBlockNumber::set(3 * timeslice);
+		assert_noop!(
+			Market::accept_swap(RuntimeOrigin::signed(3), offered),
+			Error::<Test>::RegionExpired
+		);
+
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::swaps(offered).is_none());
+		assert!(Market::swaps(expiring).is_some());
+		System::assert_has_event(Event::SwapCancelled { offered }.into());
+
+		// The offered region and the top-up are returned to the proposer, and the requested region
+		// is released:
+		assert!(!Regions::regions(offered).unwrap().locked);
+		assert!(!Regions::regions(short).unwrap().locked);
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &2), 10_000_000);
+
+		RelayBlockNumber::set(4 * timeslice);
+		Market::on_idle(1, Weight::MAX);
+		assert!(Market::swaps(expiring).is_none());
+		assert!(!Regions::regions(expiring).unwrap().locked);
+		assert!(!Regions::regions(requested).unwrap().locked);
+		assert!(Market::swaps(valid).is_some());
+		assert!(Regions::regions(other).unwrap().locked);
+	});
+}
+
+#[test]
+fn market_queries_work() {
+	new_test_ext().execute_with(|| {
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let price = 1_000;
+		let regions: Vec<RegionId> = (0..3)
+			.map(|core| RegionId { begin: 0, core, mask: CoreMask::complete() })
+			.collect();
+
+		// The first region is listed by account 1, the others by account 2.
+		for (i, region_id) in regions.iter().enumerate() {
+			let seller = if i == 0 { 1 } else { 2 };
+			assert_ok!(Regions::mint_into(&(*region_id).into(), &seller));
+			assert_ok!(Regions::set_record(
+				*region_id,
+				RegionRecord { end: 8, owner: 1, paid: None }
+			));
+		}
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(1),
+			regions[0],
+			price,
+			RELAY_ASSET_ID,
+			ListingOptions { expires_at: Some(2), ..Default::default() }
+		));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(2),
+			regions[1],
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(2),
+			regions[2],
+			price,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		// Quotes match the on-chain price:
+		RelayBlockNumber::set(1 * timeslice);
+		assert_eq!(Market::quote(regions[1], None), Some((NATIVE_ASSET_ID, 7 * price)));
+		assert_eq!(Market::quote(regions[0], None), Some((RELAY_ASSET_ID, 7 * price)));
+		assert_eq!(
+			Market::quote(regions[0], Some(NATIVE_ASSET_ID)),
+			Some((NATIVE_ASSET_ID, 2 * 7 * price))
+		);
+		assert_eq!(Market::quote(regions[1], Some(UNSUPPORTED_ASSET_ID)), None);
+		assert_eq!(
+			Market::quote(RegionId { begin: 0, core: 42, mask: CoreMask::complete() }, None),
+			None
+		);
+
+		// Listings of a seller:
+		let listings: Vec<RegionId> =
+			Market::listings_of(&2).into_iter().map(|(region_id, _)| region_id).collect();
+		assert_eq!(listings, vec![regions[1], regions[2]]);
+
+		// Pagination goes through all the listings:
+		let (first_page, next) = Market::active_listings(None, 2);
+		assert_eq!(first_page.len(), 2);
+		assert_eq!(next, Some(first_page[1].0));
+		let (second_page, next) = Market::active_listings(next, 2);
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(next, None);
+		let mut all: Vec<RegionId> = first_page
+			.into_iter()
+			.chain(second_page)
+			.map(|(region_id, _)| region_id)
+			.collect();
+		all.sort_by_key(|region_id| u128::from(*region_id));
+		let mut expected = regions.clone();
+		expected.sort_by_key(|region_id| u128::from(*region_id));
+		assert_eq!(all, expected);
+
+		// Expired listings are not active and can't be quoted:
+		RelayBlockNumber::set(2 * timeslice);
+		assert_eq!(Market::quote(regions[0], None), None);
+		let (active, _) = Market::active_listings(None, 10);
+		assert!(active.iter().all(|(id, _)| *id != regions[0]));
+		assert_eq!(active.len(), 2);
+	});
+}
+
+#[test]
+fn active_listings_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let listing = Listing {
+			seller: 2,
+			timeslice_price: 1_000,
+			asset: NATIVE_ASSET_ID,
+			sale_recipients: bounded_vec![(2, Permill::one())],
+			expires_at: None,
+			deposit: 0,
+			allowed_buyers: None,
+			min_remaining_timeslices: 0,
+		};
+		let count = MAX_SCANNED_LISTINGS + MAX_LISTINGS_PAGE;
+		let regions: Vec<RegionId> = (0..count)
+			.map(|core| RegionId { begin: 0, core: core as u16, mask: CoreMask::complete() })
+			.collect();
+		for region_id in regions.iter() {
+			Listings::<Test>::insert(region_id, listing.clone());
+		}
+
+		// None of the listings are active since the regions are unknown. The scan stops after
+		// `MAX_SCANNED_LISTINGS` listings and the next page continues from there:
+		let (page, next) = Market::active_listings(None, MAX_LISTINGS_PAGE);
+		assert!(page.is_empty());
+		assert!(next.is_some());
+		let (page, next) = Market::active_listings(next, MAX_LISTINGS_PAGE);
+		assert!(page.is_empty());
+		assert_eq!(next, None);
+
+		for region_id in regions.iter() {
+			assert_ok!(Regions::mint_into(&(*region_id).into(), &2));
+			assert_ok!(Regions::set_record(
+				*region_id,
+				RegionRecord { end: 8, owner: 1, paid: None }
+			));
+		}
+
+		// The page size is capped:
+		let (page, next) = Market::active_listings(None, u32::MAX);
+		assert_eq!(page.len(), MAX_LISTINGS_PAGE as usize);
+		assert_eq!(next, Some(page[page.len() - 1].0));
+	});
+}
+
+#[test]
+fn price_index_works() {
+	new_test_ext().execute_with(|| {
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let seller = 2;
+		let buyer = 3;
+		let full_region = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let half_region = RegionId { begin: 0, core: 1, mask: CoreMask::from_chunk(0, 40) };
+
+		for region_id in [full_region, half_region] {
+			assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+			assert_ok!(Regions::set_record(
+				region_id,
+				RegionRecord { end: 8, owner: 1, paid: None }
+			));
+		}
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			full_region,
+			1_000,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(seller),
+			half_region,
+			1_000,
+			RELAY_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		// No purchases yet:
+		assert_eq!(Market::latest_price(), None);
+		assert_eq!(Market::average_price(0), None);
+
+		// A full core for 8 timeslices at 1000 per timeslice:
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), full_region, 8_000, None));
+		assert_eq!(Market::latest_price(), Some(1_000));
+		assert_eq!(
+			Market::price_history().to_vec(),
+			vec![PricePoint { timeslice: 0, price: 1_000 }]
+		);
+
+		// Half of a core for the remaining 6 timeslices at 1000 relay chain assets per timeslice.
+		// This is 12000 in the native currency, so 4000 per timeslice of a full core.
+		RelayBlockNumber::set(2 * timeslice);
+		assert_ok!(Market::purchase_region(RuntimeOrigin::signed(buyer), half_region, 6_000, None));
+		assert_eq!(Market::latest_price(), Some(4_000));
+		assert_eq!(
+			Market::period_prices(0),
+			Some(PeriodPrice {
+				first_update: 0,
+				last_update: 2,
+				last_price: 4_000,
+				cumulative: 2 * 1_000
+			})
+		);
+
+		// 1000 for two timeslices and 4000 for one timeslice:
+		RelayBlockNumber::set(3 * timeslice);
+		assert_eq!(Market::average_price(0), Some(2_000));
+
+		// Once the bulk period is over the average doesn't change anymore:
+		RelayBlockNumber::set(10 * timeslice);
+		assert_eq!(Market::average_price(0), Some(2_500));
+		assert_eq!(Market::average_price(1), None);
+
+		// The history is bounded, the oldest purchases get dropped:
+		Market::record_price(full_region, 8, NATIVE_ASSET_ID, 16_000);
+		Market::record_price(full_region, 8, NATIVE_ASSET_ID, 24_000);
+		assert_eq!(
+			Market::price_history().to_vec(),
+			vec![
+				PricePoint { timeslice: 2, price: 4_000 },
+				PricePoint { timeslice: 10, price: 2_000 },
+				PricePoint { timeslice: 10, price: 3_000 }
+			]
+		);
+		// The new purchases are tracked in a new period:
+		assert_eq!(Market::average_price(2), Some(3_000));
+	});
+}
+
+#[test]
+fn create_and_cancel_buy_order_works() {
+	new_test_ext().execute_with(|| {
+		let buyer = 3;
+		let signer = RuntimeOrigin::signed(buyer);
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		let budget = 8_000;
+
+		// Failure: UnsupportedAsset
+		assert_noop!(
+			Market::create_buy_order(
+				signer.clone(),
+				UNSUPPORTED_ASSET_ID,
+				1_000,
+				57600,
+				0,
+				8,
+				budget
+			),
+			Error::<Test>::UnsupportedAsset
+		);
+		// Failure: InvalidBuyOrder
+		assert_noop!(
+			Market::create_buy_order(signer.clone(), NATIVE_ASSET_ID, 1_000, 57600, 8, 8, budget),
+			Error::<Test>::InvalidBuyOrder
+		);
+		assert_noop!(
+			Market::create_buy_order(signer.clone(), NATIVE_ASSET_ID, 1_000, 57601, 0, 8, budget),
+			Error::<Test>::InvalidBuyOrder
+		);
+		assert_noop!(
+			Market::create_buy_order(signer.clone(), NATIVE_ASSET_ID, 1_000, 57600, 0, 8, 0),
+			Error::<Test>::InvalidBuyOrder
+		);
+
+		let balance_old = Balances::free_balance(buyer);
+		assert_ok!(Market::create_buy_order(
+			signer.clone(),
+			NATIVE_ASSET_ID,
+			1_000,
+			57600, // Full core.
+			0,
+			8,
+			budget
+		));
+		System::assert_last_event(
+			Event::BuyOrderCreated {
+				order_id: 0,
+				buyer,
+				asset: NATIVE_ASSET_ID,
+				max_timeslice_price: 1_000,
+				min_occupancy: 57600,
+				begin: 0,
+				end: 8,
+				budget,
+			}
+			.into(),
+		);
+		assert_eq!(
+			Market::buy_orders(0),
+			Some(BuyOrder {
+				buyer,
+				asset: NATIVE_ASSET_ID,
+				max_timeslice_price: 1_000,
+				min_occupancy: 57600,
+				begin: 0,
+				end: 8,
+				budget,
+			})
+		);
+		// The budget is escrowed:
+		assert_eq!(Balances::free_balance(buyer), balance_old - budget);
+		assert_eq!(Balances::free_balance(Market::account_id()), budget);
+
+		// Failure: TooManyBuyOrders
+		assert_ok!(Market::create_buy_order(
+			signer.clone(),
+			NATIVE_ASSET_ID,
+			1_000,
+			57600, // Full core.
+			0,
+			8,
+			budget
+		));
+		assert_ok!(Market::create_buy_order(
+			signer.clone(),
+			RELAY_ASSET_ID,
+			1_000,
+			57600, // Full core.
+			0,
+			8,
+			budget
+		));
+		assert_noop!(
+			Market::create_buy_order(signer.clone(), NATIVE_ASSET_ID, 1_000, 57600, 0, 8, budget),
+			Error::<Test>::TooManyBuyOrders
+		);
+
+		// Failure: UnknownBuyOrder
+		assert_noop!(Market::cancel_buy_order(signer.clone(), 3), Error::<Test>::UnknownBuyOrder);
+		// Failure: NotAllowed
+		assert_noop!(
+			Market::cancel_buy_order(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotAllowed
+		);
+
+		assert_ok!(Market::cancel_buy_order(signer, 0));
+		System::assert_last_event(Event::BuyOrderCancelled { order_id: 0 }.into());
+		assert!(Market::buy_orders(0).is_none());
+		assert_eq!(Balances::free_balance(buyer), balance_old - budget);
+
+		// Once the order can no longer be matched anyone can cancel it:
+		RelayBlockNumber::set(8 * timeslice);
+		let relay_balance_old = Tokens::free_balance(RELAY_ASSET_ID, &buyer);
+		assert_ok!(Market::cancel_buy_order(RuntimeOrigin::signed(1), 2));
+		assert_eq!(Tokens::free_balance(RELAY_ASSET_ID, &buyer), relay_balance_old + budget);
+	});
+}
+
+#[test]
+fn buy_orders_execute_automatically() {
+	new_test_ext().execute_with(|| {
+		let seller = 2;
+		let signer = RuntimeOrigin::signed(seller);
+		let full_region = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let other_region = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		for region_id in [full_region, other_region] {
+			assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+			assert_ok!(Regions::set_record(
+				region_id,
+				RegionRecord { end: 8, owner: seller, paid: None }
+			));
+		}
+
+		let budget = 10_000;
+		assert_ok!(Market::create_buy_order(
+			RuntimeOrigin::signed(3),
+			NATIVE_ASSET_ID,
+			1_000,
+			57600, // Full core.
+			0,
+			8,
+			budget
+		));
+		assert_ok!(Market::create_buy_order(
+			RuntimeOrigin::signed(1),
+			NATIVE_ASSET_ID,
+			500,
+			28800, // Half of a core.
+			0,
+			8,
+			budget
+		));
+		let balance_buyer_old = Balances::free_balance(3);
+
+		// Too expensive for both orders:
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			full_region,
+			2_000,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert!(Market::listings(full_region).is_some());
+
+		// Repricing the region matches the first order:
+		assert_ok!(Market::update_region_price(signer.clone(), full_region, 1_000));
+		System::assert_last_event(
+			Event::BuyOrderExecuted { order_id: 0, region_id: full_region, price: 8_000 }.into(),
+		);
+		assert!(Market::listings(full_region).is_none());
+		assert!(Market::buy_orders(0).is_none());
+		assert_eq!(Regions::regions(full_region).unwrap().owner, 3);
+		// The unspent part of the budget is returned:
+		assert_eq!(Balances::free_balance(3), balance_buyer_old + budget - 8_000);
+
+		// Only one order can be executed per block:
+		assert_ok!(Market::list_region(
+			signer.clone(),
+			other_region,
+			500,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+		assert!(Market::listings(other_region).is_some());
+		assert!(Market::buy_orders(1).is_some());
+
+		System::set_block_number(2);
+		assert_ok!(Market::update_region_price(signer, other_region, 500));
+		System::assert_last_event(
+			Event::BuyOrderExecuted { order_id: 1, region_id: other_region, price: 4_000 }.into(),
+		);
+		assert_eq!(Regions::regions(other_region).unwrap().owner, 1);
+	});
+}
+
+#[test]
+fn sweep_works() {
+	new_test_ext().execute_with(|| {
+		let buyer = 3;
+		let requirements = Requirements { begin: 2, end: 8, core_occupancy: 57600 / 2 };
+
+		let full_region = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let half_region = RegionId { begin: 0, core: 1, mask: CoreMask::from_chunk(0, 40) };
+		let late_region = RegionId { begin: 4, core: 2, mask: CoreMask::complete() };
+		let relay_region = RegionId { begin: 0, core: 3, mask: CoreMask::complete() };
+		let listings = [
+			(full_region, 1, 1_000, NATIVE_ASSET_ID),
+			(half_region, 1, 800, NATIVE_ASSET_ID),
+			(late_region, 2, 100, NATIVE_ASSET_ID),
+			// Worth 1200 per timeslice in the native currency.
+			(relay_region, 2, 600, RELAY_ASSET_ID),
+		];
+		for (region_id, seller, timeslice_price, asset) in listings {
+			assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+			assert_ok!(Regions::set_record(
+				region_id,
+				RegionRecord { end: 8, owner: seller, paid: None }
+			));
+			assert_ok!(Market::list_region(
+				RuntimeOrigin::signed(seller),
+				region_id,
+				timeslice_price,
+				asset,
+				ListingOptions::default()
+			));
+		}
+		assert!(ListingIndex::<Test>::contains_key(Market::index_key(full_region, 8)));
+
+		// Failure: NoMatchingListing
+		assert_noop!(
+			Market::sweep(RuntimeOrigin::signed(buyer), requirements.clone(), 6_000),
+			Error::<Test>::NoMatchingListing
+		);
+		// The seller's own regions are not considered:
+		assert_noop!(
+			Market::sweep(
+				RuntimeOrigin::signed(1),
+				Requirements { begin: 0, end: 8, core_occupancy: 57600 },
+				9_000
+			),
+			Error::<Test>::NoMatchingListing
+		);
+
+		// The late region is the cheapest, but doesn't begin early enough:
+		let balance_old = Balances::free_balance(buyer);
+		assert_ok!(Market::sweep(RuntimeOrigin::signed(buyer), requirements.clone(), 10_000));
+		assert_eq!(Regions::regions(half_region).unwrap().owner, buyer);
+		assert_eq!(Balances::free_balance(buyer), balance_old - 6_400);
+		assert!(!ListingIndex::<Test>::contains_key(Market::index_key(half_region, 8)));
+
+		// The region priced in the relay chain asset is more expensive in the native currency:
+		assert_ok!(Market::sweep(RuntimeOrigin::signed(buyer), requirements, 10_000));
+		assert_eq!(Regions::regions(full_region).unwrap().owner, buyer);
+		assert_eq!(Balances::free_balance(buyer), balance_old - 6_400 - 8_000);
+	});
+}
+
+#[test]
+fn sweep_only_considers_regions_lasting_long_enough() {
+	new_test_ext().execute_with(|| {
+		let buyer = 3;
+		let requirements = Requirements { begin: 2, end: 8, core_occupancy: 57600 };
+
+		// More listings than `MaxSweepCandidates` which don't match the requirements. The
+		// short regions end too soon and the late regions begin too late.
+		let mut core = 0;
+		for seller in 10..22 {
+			assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), seller, 1_000));
+			for _ in 0..2 {
+				let (begin, end) = if seller < 16 { (0, 4) } else { (6, 8) };
+				let region_id = RegionId { begin, core, mask: CoreMask::complete() };
+				core += 1;
+				assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+				assert_ok!(Regions::set_record(
+					region_id,
+					RegionRecord { end, owner: seller, paid: None }
+				));
+				assert_ok!(Market::list_region(
+					RuntimeOrigin::signed(seller),
+					region_id,
+					1,
+					NATIVE_ASSET_ID,
+					ListingOptions::default()
+				));
+			}
+		}
+
+		let matching = RegionId { begin: 0, core, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&matching.into(), &2));
+		assert_ok!(Regions::set_record(matching, RegionRecord { end: 10, owner: 2, paid: None }));
+		assert_ok!(Market::list_region(
+			RuntimeOrigin::signed(2),
+			matching,
+			1_000,
+			NATIVE_ASSET_ID,
+			ListingOptions::default()
+		));
+
+		assert_ok!(Market::sweep(RuntimeOrigin::signed(buyer), requirements, 10_000));
+		assert_eq!(Regions::regions(matching).unwrap().owner, buyer);
+	});
+}
+
+#[test]
+fn migrations_work() {
+	use crate::migrations::*;
+	use frame_support::traits::{ConstU32, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	type Migrations = (
+		v1::MigrateToV1<Test>,
+		v2::MigrateToV2<Test, ConstU32<RELAY_ASSET_ID>>,
+		v3::MigrateToV3<Test>,
+		v4::MigrateToV4<Test>,
+		v5::MigrateToV5<Test>,
+		v6::MigrateToV6<Test>,
+		v7::MigrateToV7<Test>,
+	);
+
+	new_test_ext().execute_with(|| {
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let seller = 2;
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(
+			region_id,
+			RegionRecord { end: 8, owner: seller, paid: None }
+		));
+
+		StorageVersion::new(0).put::<Market>();
+		frame_support::storage::unhashed::put(
+			&Listings::<Test>::hashed_key_for(region_id),
+			&v0::Listing { seller, timeslice_price: 1_000u64, sale_recipient: 3u64 },
+		);
+
+		Migrations::on_runtime_upgrade();
+
+		assert_eq!(Market::on_chain_storage_version(), StorageVersion::new(7));
+		assert_eq!(
+			Market::listings(region_id),
+			Some(Listing {
+				seller,
+				timeslice_price: 1_000,
+				asset: RELAY_ASSET_ID,
+				sale_recipients: bounded_vec![(3, Permill::one())],
+				expires_at: None,
+				deposit: 0,
+				allowed_buyers: None,
+				min_remaining_timeslices: 0,
+			})
+		);
+		assert_eq!(Market::seller_listings(seller).into_inner(), vec![region_id]);
+		assert!(ListingIndex::<Test>::contains_key(Market::index_key(region_id, 8)));
+
+		// The migrated listing can be purchased in the relay chain currency:
+		assert_eq!(Market::quote(region_id, None), Some((RELAY_ASSET_ID, 8_000)));
+	});
+}
+
+#[test]
+fn migrate_to_v6_works() {
+	use crate::migrations::{v4, v6::MigrateToV6};
+	use frame_support::traits::{
+		fungible::Mutate as _, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+	};
+
+	new_test_ext().execute_with(|| {
+		let seller = 2;
+		let deposit = 100;
+		let listing = |core: u16, deposit: u64| {
+			let region_id = RegionId { begin: 0, core, mask: CoreMask::complete() };
+			v4::Listings::<Test>::insert(
+				region_id,
+				v4::Listing {
+					seller,
+					timeslice_price: 1_000,
+					asset: NATIVE_ASSET_ID,
+					sale_recipients: bounded_vec![(seller, Permill::one())],
+					expires_at: None,
+					deposit,
+					allowed_buyers: None,
+				},
+			);
+			region_id
+		};
+		// The market account holds the deposit of the first listing only.
+		let with_deposit = listing(0, deposit);
+		let without_deposit = listing(1, 0);
+		let missing_deposit = listing(2, deposit);
+		assert_ok!(Balances::mint_into(&Market::account_id(), deposit));
+
+		StorageVersion::new(5).put::<Market>();
+		let seller_balance = Balances::free_balance(seller);
+		MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Market::on_chain_storage_version(), StorageVersion::new(6));
+		// The deposit is held on the seller instead:
+		assert_eq!(Balances::free_balance(Market::account_id()), 0);
+		assert_eq!(Balances::free_balance(seller), seller_balance);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::ListingDeposit.into(), &seller), deposit);
+		assert_eq!(v4::Listings::<Test>::get(with_deposit).unwrap().deposit, deposit);
+		assert_eq!(v4::Listings::<Test>::get(without_deposit).unwrap().deposit, 0);
+		// A deposit which can't be moved is dropped from the listing:
+		assert_eq!(v4::Listings::<Test>::get(missing_deposit).unwrap().deposit, 0);
+	});
+}
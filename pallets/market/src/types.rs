@@ -13,21 +13,210 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 use crate::BalanceOf;
-use codec::{Decode, Encode, MaxEncodedLen};
-use scale_info::TypeInfo;
+use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, DefaultNoBound};
+use pallet_broker::{PartsOf57600, RegionId, Timeslice};
+use scale_info::{Type, TypeInfo};
+use sp_runtime::{DispatchResult, Permill};
 
 pub type RegionRecordOf<T> =
 	pallet_broker::RegionRecord<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
 
+pub type ListingOf<T> =
+	Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as crate::Config>::AssetId>;
+
+pub type ListingOptionsOf<T> = ListingOptions<<T as frame_system::Config>::AccountId>;
+
+pub type BuyOrderOf<T> =
+	BuyOrder<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as crate::Config>::AssetId>;
+
+/// A listing along with the asset and the amount to be paid for the listed region.
+pub type PurchaseQuoteOf<T> = (ListingOf<T>, <T as crate::Config>::AssetId, BalanceOf<T>);
+
+/// Buy order index.
+pub type BuyOrderId = u32;
+
+/// The maximum number of buyers a private listing can be restricted to.
+pub const MAX_ALLOWED_BUYERS: u32 = 16;
+
+/// The accounts allowed to purchase a private listing.
+pub type AllowedBuyers<AccountId> = BoundedVec<AccountId, ConstU32<MAX_ALLOWED_BUYERS>>;
+
+/// The maximum number of accounts the sale revenue can be split among.
+pub const MAX_SALE_RECIPIENTS: u32 = 8;
+
+/// The accounts receiving the sale revenue along with their share of it.
+pub type SaleRecipients<AccountId> =
+	BoundedVec<(AccountId, Permill), ConstU32<MAX_SALE_RECIPIENTS>>;
+
+/// The amounts paid to the sale recipients when a region got sold.
+pub type SalePayments<AccountId, Balance> =
+	BoundedVec<(AccountId, Balance), ConstU32<MAX_SALE_RECIPIENTS>>;
+
 /// The information we store about a region that got listed on sale.
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
-pub struct Listing<AccountId, Balance> {
+pub struct Listing<AccountId, Balance, AssetId> {
 	/// The `AccountId` selling the region.
 	pub seller: AccountId,
 	/// The price per a single timeslice.
 	pub timeslice_price: Balance,
-	/// The `AccountId` receiving the payment from the sale.
+	/// The asset in which the region is priced.
+	pub asset: AssetId,
+	/// The accounts receiving the payment from the sale along with their share of it.
+	///
+	/// The shares always add up to 100%. This will usually be just the seller account.
+	pub sale_recipients: SaleRecipients<AccountId>,
+	/// The timeslice at which the listing expires.
+	///
+	/// Once expired the region can no longer be purchased and anyone can remove the listing. If
+	/// `None` the listing stays valid until the region itself expires.
+	pub expires_at: Option<Timeslice>,
+	/// The deposit taken from the seller when listing the region.
+	///
+	/// Returned to the seller once the listing is removed.
+	pub deposit: Balance,
+	/// The accounts allowed to purchase the region.
 	///
-	/// This will usually be the seller account.
-	pub sale_recipient: AccountId,
+	/// If `None` anyone can purchase the region.
+	pub allowed_buyers: Option<AllowedBuyers<AccountId>>,
+	/// The minimum number of timeslices the region has to remain usable for to be sold.
+	///
+	/// Once fewer timeslices remain the listing goes stale and can no longer be purchased. Stale
+	/// listings are treated as expired, and get unlisted with `UnlistReason::Stale` by the
+	/// `on_idle` sweep.
+	pub min_remaining_timeslices: Timeslice,
+}
+
+/// The optional settings of a new listing.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, DefaultNoBound)]
+pub struct ListingOptions<AccountId> {
+	/// The accounts receiving the payment from the sale along with their share of it.
+	///
+	/// The shares must add up to 100%. If `None` the seller receives the whole payment.
+	pub sale_recipients: Option<SaleRecipients<AccountId>>,
+	/// The timeslice at which the listing expires.
+	///
+	/// If `None` the listing is valid until the region expires.
+	pub expires_at: Option<Timeslice>,
+	/// The accounts allowed to purchase the region.
+	///
+	/// If `None`, or if empty, anyone can purchase the region.
+	pub allowed_buyers: Option<AllowedBuyers<AccountId>>,
+	/// The minimum number of timeslices the region has to remain usable for to be sold.
+	///
+	/// Zero allows selling the region until its last timeslice.
+	pub min_remaining_timeslices: Timeslice,
+}
+
+/// A timeslice encoded in big-endian byte order.
+///
+/// Used as a key of `Identity` hashed maps, so the keys are ordered by timeslice and ranges of
+/// timeslices can be iterated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, MaxEncodedLen)]
+pub struct OrderedTimeslice(pub Timeslice);
+
+impl Encode for OrderedTimeslice {
+	fn size_hint(&self) -> usize {
+		core::mem::size_of::<Timeslice>()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		f(&self.0.to_be_bytes())
+	}
+}
+
+impl EncodeLike for OrderedTimeslice {}
+
+impl Decode for OrderedTimeslice {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		<[u8; 4]>::decode(input).map(|bytes| Self(Timeslice::from_be_bytes(bytes)))
+	}
+}
+
+impl TypeInfo for OrderedTimeslice {
+	type Identity = [u8; 4];
+
+	fn type_info() -> Type {
+		<[u8; 4]>::type_info()
+	}
+}
+
+/// The reason a region got removed from the market.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum UnlistReason {
+	/// The seller removed the listing.
+	Seller,
+	/// The listing or the listed region expired.
+	Expired,
+	/// Fewer timeslices remain in the region than the seller's minimum.
+	Stale,
+	/// The region got purchased.
+	Purchased,
+}
+
+pub type SwapOf<T> =
+	Swap<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as crate::Config>::AssetId>;
+
+/// A standing order for purchasing a region as soon as a matching one gets listed.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct BuyOrder<AccountId, Balance, AssetId> {
+	/// The account placing the order.
+	pub buyer: AccountId,
+	/// The asset in which the region has to be priced.
+	pub asset: AssetId,
+	/// The maximum price per timeslice the buyer is willing to pay.
+	pub max_timeslice_price: Balance,
+	/// The minimum fraction of the core the region has to occupy.
+	pub min_occupancy: PartsOf57600,
+	/// The earliest timeslice at which the region can begin.
+	pub begin: Timeslice,
+	/// The latest timeslice at which the region can end.
+	pub end: Timeslice,
+	/// The funds escrowed for the purchase.
+	///
+	/// The total price of a matching region can't exceed this amount.
+	pub budget: Balance,
+}
+
+/// A proposal for swapping a region for another region.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct Swap<AccountId, Balance, AssetId> {
+	/// The `AccountId` proposing the swap and offering its region.
+	pub proposer: AccountId,
+	/// The region the proposer wants to get in return.
+	pub requested: RegionId,
+	/// An optional payment the proposer adds on top of the offered region.
+	///
+	/// The amount is held by the market until the swap is accepted or cancelled.
+	pub top_up: Option<(AssetId, Balance)>,
+	/// The timeslice at which the proposal expires.
+	pub expires_at: Timeslice,
+}
+
+/// A coretime purchase recorded in the price history.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PricePoint<Balance> {
+	/// The timeslice in which the purchase happened.
+	pub timeslice: Timeslice,
+	/// The price paid per timeslice of a full core, in the native currency.
+	pub price: Balance,
+}
+
+/// Data for calculating the time-weighted average price during a bulk period.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PeriodPrice<Balance> {
+	/// The timeslice of the first purchase in the bulk period.
+	pub first_update: Timeslice,
+	/// The timeslice of the latest purchase in the bulk period.
+	pub last_update: Timeslice,
+	/// The price of the latest purchase.
+	pub last_price: Balance,
+	/// The sum of the prices weighted by the number of timeslices each of them was the latest
+	/// price, up until `last_update`.
+	pub cumulative: Balance,
+}
+
+pub trait FeeHandler<AccountId, AssetId, Balance> {
+	/// Function responsible for handling how we deal with fees.
+	fn handle(who: &AccountId, asset: AssetId, fee: Balance) -> DispatchResult;
 }
@@ -13,35 +13,11 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-//! Autogenerated weights for `pallet_market`
+//! Estimated weights for `pallet_market`.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2024-06-02, STEPS: `20`, REPEAT: `50`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `sergej-B650-AORUS-ELITE-AX`, CPU: `AMD Ryzen 9 7900X3D 12-Core Processor`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("cocos")`, DB CACHE: `1024`
-
-// Executed Command:
-// ./target/release/regionx-node
-// benchmark
-// pallet
-// --chain
-// cocos
-// --pallet
-// pallet_market
-// --steps
-// 20
-// --repeat
-// 50
-// --output
-// ./pallets/market/src/weights.rs
-// --header
-// ./config/HEADER-GPL3
-// --template
-// ./config/frame-weight-template.hbs
-// --extrinsic=*
-// --wasm-execution=compiled
-// --heap-pages=4096
+//! NOTE: These weights were not produced by the benchmark CLI. They are estimates based on the
+//! benchmarks of comparable calls and must be regenerated by running `benchmark pallet` for
+//! `pallet_market` before they are relied upon.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -57,47 +33,73 @@ pub trait WeightInfo {
 	fn unlist_region() -> Weight;
 	fn update_region_price() -> Weight;
 	fn purchase_region() -> Weight;
+	fn set_market_fee() -> Weight;
+	fn remove_expired_listing() -> Weight;
+	fn purchase_many(n: u32) -> Weight;
+	fn propose_swap() -> Weight;
+	fn accept_swap() -> Weight;
+	fn cancel_swap() -> Weight;
+	fn create_buy_order() -> Weight;
+	fn cancel_buy_order() -> Weight;
+	fn match_buy_orders(n: u32) -> Weight;
+	fn sweep(n: u32) -> Weight;
 }
 
 /// Weights for `pallet_market` using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	fn list_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `325`
 		//  Estimated: `3584`
 		// Minimum execution time: 16_151_000 picoseconds.
 		Weight::from_parts(16_621_000, 3584)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:3)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	fn unlist_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `465`
 		//  Estimated: `3584`
 		// Minimum execution time: 17_323_000 picoseconds.
 		Weight::from_parts(17_773_000, 3584)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:0)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
@@ -114,66 +116,347 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Storage: `Tokens::Accounts` (r:7 w:7)
 	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
 	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
 	fn purchase_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `924`
-		//  Estimated: `6156`
+		//  Estimated: `24237`
 		// Minimum execution time: 43_984_000 picoseconds.
-		Weight::from_parts(45_055_000, 6156)
+		Weight::from_parts(45_055_000, 24237)
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: `Market::MarketFee` (r:0 w:1)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_market_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_310_000 picoseconds.
+		Weight::from_parts(5_560_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Market::Listings` (r:0 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
+	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	fn remove_expired_listing() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1020`
+		//  Estimated: `6156`
+		// Minimum execution time: 38_410_000 picoseconds.
+		Weight::from_parts(39_602_000, 6156)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Market::Listings` (r:10 w:10)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:10 w:10)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:20 w:20)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:10 w:10)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:10 w:10)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:10 w:10)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:10)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn purchase_many(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `760 + n * (215 ±0)`
+		//  Estimated: `6156 + n * (22199 ±0)`
+		// Minimum execution time: 46_120_000 picoseconds.
+		Weight::from_parts(12_310_000, 6156)
+			// Standard Error: 41_302
+			.saturating_add(Weight::from_parts(38_415_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((18_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((18_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 22199).saturating_mul(n.into()))
+	}
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Swaps` (r:0 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	fn propose_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6156`
+		// Minimum execution time: 32_480_000 picoseconds.
+		Weight::from_parts(33_612_000, 6156)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::Swaps` (r:1 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn accept_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `743`
+		//  Estimated: `6156`
+		// Minimum execution time: 41_230_000 picoseconds.
+		Weight::from_parts(42_118_000, 6156)
 			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::Swaps` (r:1 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn cancel_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `611`
+		//  Estimated: `6156`
+		// Minimum execution time: 29_954_000 picoseconds.
+		Weight::from_parts(30_741_000, 6156)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
+	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::NextBuyOrderId` (r:1 w:1)
+	/// Proof: `Market::NextBuyOrderId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::BuyOrders` (r:0 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	fn create_buy_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6156`
+		// Minimum execution time: 31_402_000 picoseconds.
+		Weight::from_parts(32_180_000, 6156)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::BuyOrders` (r:1 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn cancel_buy_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `598`
+		//  Estimated: `6156`
+		// Minimum execution time: 28_761_000 picoseconds.
+		Weight::from_parts(29_533_000, 6156)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `Market::AutoPurchases` (r:1 w:1)
+	/// Proof: `Market::AutoPurchases` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Listings` (r:1 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Market::BuyOrders` (r:101 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:11 w:11)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn match_buy_orders(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1160 + n * (97 ±0)`
+		//  Estimated: `29421 + n * (2567 ±0)`
+		// Minimum execution time: 84_310_000 picoseconds.
+		Weight::from_parts(81_926_000, 29421)
+			// Standard Error: 4_127
+			.saturating_add(Weight::from_parts(4_603_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(23_u64))
+			.saturating_add(Weight::from_parts(0, 2567).saturating_mul(n.into()))
+	}
+	/// Storage: `Market::ListingIndex` (r:101 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::Listings` (r:100 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:100 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:10 w:10)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn sweep(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1296 + n * (325 ±0)`
+		//  Estimated: `24237 + n * (3387 ±0)`
+		// Minimum execution time: 72_845_000 picoseconds.
+		Weight::from_parts(58_417_000, 24237)
+			// Standard Error: 6_981
+			.saturating_add(Weight::from_parts(14_872_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(18_u64))
+			.saturating_add(Weight::from_parts(0, 3387).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	fn list_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `325`
 		//  Estimated: `3584`
 		// Minimum execution time: 16_151_000 picoseconds.
 		Weight::from_parts(16_621_000, 3584)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:3)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	fn unlist_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `465`
 		//  Estimated: `3584`
 		// Minimum execution time: 17_323_000 picoseconds.
 		Weight::from_parts(17_773_000, 3584)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:0)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
@@ -190,24 +473,289 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	/// Storage: `Market::Listings` (r:1 w:1)
-	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
 	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
 	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Storage: `Tokens::Accounts` (r:7 w:7)
 	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
 	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
 	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
 	fn purchase_region() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `924`
-		//  Estimated: `6156`
+		//  Estimated: `24237`
 		// Minimum execution time: 43_984_000 picoseconds.
-		Weight::from_parts(45_055_000, 6156)
+		Weight::from_parts(45_055_000, 24237)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: `Market::MarketFee` (r:0 w:1)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_market_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_310_000 picoseconds.
+		Weight::from_parts(5_560_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Market::Listings` (r:0 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
+	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	fn remove_expired_listing() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1020`
+		//  Estimated: `6156`
+		// Minimum execution time: 38_410_000 picoseconds.
+		Weight::from_parts(39_602_000, 6156)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Market::Listings` (r:10 w:10)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:10 w:10)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:20 w:20)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:10 w:10)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:10 w:10)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:10 w:10)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:10)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn purchase_many(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `760 + n * (215 ±0)`
+		//  Estimated: `6156 + n * (22199 ±0)`
+		// Minimum execution time: 46_120_000 picoseconds.
+		Weight::from_parts(12_310_000, 6156)
+			// Standard Error: 41_302
+			.saturating_add(Weight::from_parts(38_415_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((18_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((18_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 22199).saturating_mul(n.into()))
+	}
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Swaps` (r:0 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	fn propose_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6156`
+		// Minimum execution time: 32_480_000 picoseconds.
+		Weight::from_parts(33_612_000, 6156)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::Swaps` (r:1 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn accept_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `743`
+		//  Estimated: `6156`
+		// Minimum execution time: 41_230_000 picoseconds.
+		Weight::from_parts(42_118_000, 6156)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::Swaps` (r:1 w:1)
+	/// Proof: `Market::Swaps` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Regions::Regions` (r:2 w:2)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn cancel_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `611`
+		//  Estimated: `6156`
+		// Minimum execution time: 29_954_000 picoseconds.
+		Weight::from_parts(30_741_000, 6156)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetRegistry::Metadata` (r:1 w:0)
+	/// Proof: `AssetRegistry::Metadata` (`max_values`: None, `max_size`: Some(737), added: 3212, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::NextBuyOrderId` (r:1 w:1)
+	/// Proof: `Market::NextBuyOrderId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::BuyOrders` (r:0 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	fn create_buy_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6156`
+		// Minimum execution time: 31_402_000 picoseconds.
+		Weight::from_parts(32_180_000, 6156)
 			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Market::BuyOrders` (r:1 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	fn cancel_buy_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `598`
+		//  Estimated: `6156`
+		// Minimum execution time: 28_761_000 picoseconds.
+		Weight::from_parts(29_533_000, 6156)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: `Market::AutoPurchases` (r:1 w:1)
+	/// Proof: `Market::AutoPurchases` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Listings` (r:1 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Market::BuyOrders` (r:101 w:1)
+	/// Proof: `Market::BuyOrders` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `Market::CounterForBuyOrders` (r:1 w:1)
+	/// Proof: `Market::CounterForBuyOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:11 w:11)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn match_buy_orders(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1160 + n * (97 ±0)`
+		//  Estimated: `29421 + n * (2567 ±0)`
+		// Minimum execution time: 84_310_000 picoseconds.
+		Weight::from_parts(81_926_000, 29421)
+			// Standard Error: 4_127
+			.saturating_add(Weight::from_parts(4_603_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(23_u64))
+			.saturating_add(Weight::from_parts(0, 2567).saturating_mul(n.into()))
+	}
+	/// Storage: `Market::ListingIndex` (r:101 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::Listings` (r:100 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(912), added: 3387, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:100 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:10 w:10)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn sweep(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1296 + n * (325 ±0)`
+		//  Estimated: `24237 + n * (3387 ±0)`
+		// Minimum execution time: 72_845_000 picoseconds.
+		Weight::from_parts(58_417_000, 24237)
+			// Standard Error: 6_981
+			.saturating_add(Weight::from_parts(14_872_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
+			.saturating_add(Weight::from_parts(0, 3387).saturating_mul(n.into()))
+	}
 }
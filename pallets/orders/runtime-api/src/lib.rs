@@ -0,0 +1,54 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for Coretime orders.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use order_primitives::{Order, OrderId, ParaId};
+use pallet_broker::RegionId;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// API for querying Coretime orders.
+	///
+	/// Open orders are the ones which are not expired yet.
+	pub trait OrdersApi<AccountId, Balance, AssetId>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		AssetId: Codec,
+	{
+		/// Returns up to `limit` open orders, starting after the `start_after` order.
+		fn orders(start_after: Option<OrderId>, limit: u32) -> Vec<(OrderId, Order<AccountId, Balance>)>;
+
+		/// Returns the open orders created for a parachain.
+		fn para_orders(para_id: ParaId) -> Vec<(OrderId, Order<AccountId, Balance>)>;
+
+		/// Returns the open orders created by an account.
+		fn creator_orders(creator: AccountId) -> Vec<(OrderId, Order<AccountId, Balance>)>;
+
+		/// Returns the sum of all contributions made to an order, along with the sum of the
+		/// contributions made in each of the other assets.
+		fn total_contributions(order_id: OrderId) -> (Balance, Vec<(AssetId, Balance)>);
+
+		/// Returns the contribution an account made to an order.
+		fn contribution(order_id: OrderId, who: AccountId) -> Balance;
+
+		/// Returns whether a region satisfies the requirements of an order.
+		fn region_matches(order_id: OrderId, region_id: RegionId) -> bool;
+	}
+}
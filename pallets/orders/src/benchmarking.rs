@@ -20,7 +20,7 @@
 use super::*;
 
 use frame_benchmarking::v2::*;
-use frame_support::traits::Get;
+use frame_support::traits::EnsureOrigin;
 use frame_system::RawOrigin;
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
@@ -47,7 +47,7 @@ mod benchmarks {
 			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
 		);
 		#[extrinsic_call]
-		_(RawOrigin::Signed(caller.clone()), para_id, requirements);
+		_(RawOrigin::Signed(caller.clone()), para_id, requirements, None);
 
 		assert_last_event::<T>(Event::OrderCreated { order_id: 0, by: caller }.into());
 
@@ -61,19 +61,22 @@ mod benchmarks {
 		let para_id: ParaId = 2000.into();
 		let requirements = Requirements {
 			begin: 0,
-			end: 0,
+			end: 8,
 			core_occupancy: 28800, // Half of a core.
 		};
 
 		<T as crate::Config>::Currency::make_free_balance_be(
 			&caller.clone(),
-			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+			<T as crate::Config>::OrderCreationCost::get() * 3u32.into(),
 		);
 		crate::Pallet::<T>::create_order(
 			RawOrigin::Signed(caller.clone()).into(),
 			para_id,
 			requirements,
+			None,
 		)?;
+		// The worst case is the creator cancelling the order early and paying the penalty.
+		CancellationPenalty::<T>::put(<T as crate::Config>::OrderCreationCost::get());
 
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller.clone()), 0);
@@ -104,6 +107,7 @@ mod benchmarks {
 			RawOrigin::Signed(creator.clone()).into(),
 			para_id,
 			requirements,
+			None,
 		)?;
 
 		#[extrinsic_call]
@@ -142,13 +146,14 @@ mod benchmarks {
 			RawOrigin::Signed(creator.clone()).into(),
 			para_id,
 			requirements,
+			None,
 		)?;
 		crate::Pallet::<T>::contribute(
 			RawOrigin::Signed(creator.clone()).into(),
 			0,
 			<T as crate::Config>::MinimumContribution::get(),
 		)?;
-		crate::Pallet::<T>::do_cancel_order(0, 10)?;
+		crate::Pallet::<T>::do_cancel_order(0, &creator, 10)?;
 
 		#[extrinsic_call]
 		_(RawOrigin::Signed(creator.clone()), 0);
@@ -165,5 +170,316 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn create_recurring_order() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+		let reward = <T as crate::Config>::MinimumContribution::get();
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&caller.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+		);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), para_id, requirements, reward);
+
+		assert_last_event::<T>(Event::RecurringOrderCreated { order_id: 0, reward }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_cancellation_penalty() -> Result<(), BenchmarkError> {
+		let origin = T::CancellationPenaltyOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let penalty = <T as crate::Config>::OrderCreationCost::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, penalty);
+
+		assert_last_event::<T>(Event::CancellationPenaltyUpdated { penalty }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_order() -> Result<(), BenchmarkError> {
+		let creator: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&creator.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(creator.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 57600, // Full core.
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), 0, requirements.clone());
+
+		assert_last_event::<T>(Event::OrderUpdated { order_id: 0, requirements }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_funding_target() -> Result<(), BenchmarkError> {
+		let creator: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&creator.clone(),
+			(<T as crate::Config>::OrderCreationCost::get() +
+				<T as crate::Config>::MinimumContribution::get()) *
+				2u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(creator.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+		crate::Pallet::<T>::contribute(
+			RawOrigin::Signed(creator.clone()).into(),
+			0,
+			<T as crate::Config>::MinimumContribution::get(),
+		)?;
+
+		// Setting a reached target is the worst case since it emits an additional event.
+		let total = <T as crate::Config>::MinimumContribution::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), 0, Some(total));
+
+		assert_last_event::<T>(Event::OrderFullyFunded { order_id: 0, total }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn contribute_asset() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&caller.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+
+		let asset = T::BenchmarkAssetId::get();
+		let amount = <T as crate::Config>::MinimumContribution::get();
+		T::Assets::deposit(asset, &caller, amount * 2u32.into())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, asset, amount);
+
+		assert_last_event::<T>(
+			Event::AssetContributed { order_id: 0, who: caller, asset, amount }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_asset_contribution() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&caller.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+
+		let asset = T::BenchmarkAssetId::get();
+		let amount = <T as crate::Config>::MinimumContribution::get();
+		T::Assets::deposit(asset, &caller, amount * 2u32.into())?;
+		crate::Pallet::<T>::contribute_asset(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			asset,
+			amount,
+		)?;
+		crate::Pallet::<T>::do_cancel_order(0, &caller, 10)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, asset);
+
+		assert_last_event::<T>(
+			Event::AssetContributionRemoved { order_id: 0, who: caller, asset, amount }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clean_up_order() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&caller.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+
+		let asset = T::BenchmarkAssetId::get();
+		let amount = <T as crate::Config>::MinimumContribution::get();
+		T::Assets::deposit(asset, &caller, amount * 2u32.into())?;
+		crate::Pallet::<T>::contribute_asset(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			asset,
+			amount,
+		)?;
+		crate::Pallet::<T>::do_cancel_order(0, &caller, 10)?;
+
+		// Worst case: there are funds left over in both the native currency and the asset.
+		let order_account = T::OrderToAccountId::convert(0);
+		<T as crate::Config>::Currency::make_free_balance_be(&order_account, amount);
+
+		#[block]
+		{
+			crate::Pallet::<T>::clean_up_order(0, &caller);
+		}
+
+		assert_last_event::<T>(Event::OrderRefunded { order_id: 0 }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_order_via_xcm() -> Result<(), BenchmarkError> {
+		let origin =
+			T::XcmOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let location =
+			T::XcmOrigin::ensure_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+		let sovereign_account = T::LocationToAccountId::convert_location(&location)
+			.ok_or(BenchmarkError::Weightless)?;
+
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+		let amount = <T as crate::Config>::MinimumContribution::get();
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&sovereign_account,
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into() + amount * 2u32.into(),
+		);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, requirements, amount);
+
+		assert_last_event::<T>(
+			Event::Contributed { order_id: 0, who: sovereign_account, amount }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_contribution() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let receiver: T::AccountId = account("receiver", 0, 0);
+
+		let para_id: ParaId = 2000.into();
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+		let amount = <T as crate::Config>::MinimumContribution::get();
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&caller.clone(),
+			<T as crate::Config>::OrderCreationCost::get() * 2u32.into() + amount * 3u32.into(),
+		);
+		crate::Pallet::<T>::create_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			para_id,
+			requirements,
+			None,
+		)?;
+		crate::Pallet::<T>::contribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			amount * 2u32.into(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, receiver.clone(), amount);
+
+		assert_last_event::<T>(
+			Event::ContributionTransferred { order_id: 0, from: caller, to: receiver, amount }
+				.into(),
+		);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(vec![]), crate::mock::Test);
 }
@@ -15,14 +15,20 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::{Currency, ExistenceRequirement};
-use order_primitives::{Order, OrderFactory, OrderId, OrderInspect, Requirements};
+use frame_support::traits::{Contains, Currency, ExistenceRequirement, Get};
+use order_primitives::{
+	Order, OrderFactory, OrderFulfillment, OrderId, OrderInspect, Requirements,
+};
+use orml_traits::MultiCurrency;
 pub use pallet::*;
 use pallet_broker::Timeslice;
+use scale_info::prelude::vec::Vec;
 use sp_runtime::{
-	traits::{BlockNumberProvider, Convert},
-	SaturatedConversion,
+	traits::{BlockNumberProvider, Convert, Saturating, Zero},
+	Perbill, SaturatedConversion,
 };
+use xcm::latest::{Junction, Junctions, MultiLocation};
+use xcm_executor::traits::ConvertLocation;
 
 #[cfg(test)]
 mod mock;
@@ -30,29 +36,42 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod xcm_mock;
+
+#[cfg(test)]
+mod xcm_tests;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
 mod types;
 pub use crate::types::*;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
 pub type BalanceOf<T> =
 	<<T as crate::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type OrderOf<T> = Order<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
 /// Relay chain block number.
 pub type RCBlockNumberOf<T> =
 	<<T as crate::Config>::RCBlockNumberProvider as BlockNumberProvider>::BlockNumber;
 
+const LOG_TARGET: &str = "runtime::orders";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::{
 		pallet_prelude::*,
 		sp_runtime::Saturating,
+		storage::with_storage_layer,
 		traits::{fungible::Mutate, Get, ReservableCurrency},
+		weights::WeightMeter,
 	};
 	use frame_system::pallet_prelude::*;
 
@@ -68,6 +87,40 @@ pub mod pallet {
 		/// Type responsible for dealing with order creation fees.
 		type OrderCreationFeeHandler: FeeHandler<Self::AccountId, BalanceOf<Self>>;
 
+		/// Type responsible for dealing with the penalty the creator pays for cancelling an order
+		/// early.
+		type CancellationPenaltyHandler: FeeHandler<Self::AccountId, BalanceOf<Self>>;
+
+		/// The origin which can update the order cancellation penalty.
+		type CancellationPenaltyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Identifier of the assets orders can be funded with besides `Currency`.
+		type AssetId: Parameter + Member + Copy + MaxEncodedLen;
+
+		/// Multi-currency implementation used for contributions in other assets.
+		type Assets: MultiCurrency<
+			Self::AccountId,
+			CurrencyId = Self::AssetId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// The assets orders can be funded with besides `Currency`.
+		type SupportedAssets: Contains<Self::AssetId>;
+
+		/// The maximum number of other assets a single order can be funded with.
+		#[pallet::constant]
+		type MaxOrderAssets: Get<u32>;
+
+		/// A supported asset used for benchmarking contributions in other assets.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkAssetId: Get<Self::AssetId>;
+
+		/// The origin of XCM messages, resolving to the location the message was sent from.
+		type XcmOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+
+		/// Converts the location of an XCM origin into an account on this chain.
+		type LocationToAccountId: ConvertLocation<Self::AccountId>;
+
 		/// Type for getting the current relay chain block.
 		///
 		/// This is used for determining the current timeslice.
@@ -92,17 +145,42 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinimumContribution: Get<BalanceOf<Self>>;
 
+		/// The number of timeslices by which recurring orders get renewed after a fulfilment.
+		#[pallet::constant]
+		type BulkPeriod: Get<Timeslice>;
+
 		/// Weight Info
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Created orders.
 	#[pallet::storage]
 	#[pallet::getter(fn orders)]
-	pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, Order<T::AccountId>>;
+	pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>>;
+
+	/// Index of the orders created for each parachain.
+	#[pallet::storage]
+	pub type ParaOrders<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, ParaId, Blake2_128Concat, OrderId, (), OptionQuery>;
+
+	/// Index of the orders created by each account.
+	#[pallet::storage]
+	pub type CreatorOrders<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		OrderId,
+		(),
+		OptionQuery,
+	>;
 
 	/// Next order id.
 	#[pallet::storage]
@@ -122,6 +200,69 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Crowdfunding contributions made in assets other than `Currency`.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_contributions)]
+	pub type AssetContributions<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, OrderId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AssetId>,
+		),
+		BalanceOf<T>, // contributed amount
+		ValueQuery,
+	>;
+
+	/// The sum of all contributions made to an order in each of the assets other than
+	/// `Currency`.
+	#[pallet::storage]
+	#[pallet::getter(fn total_asset_contributions)]
+	pub type TotalAssetContributions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		OrderId,
+		BoundedVec<(T::AssetId, BalanceOf<T>), T::MaxOrderAssets>,
+		ValueQuery,
+	>;
+
+	/// The penalty the creator pays for cancelling an order before it expires.
+	#[pallet::storage]
+	#[pallet::getter(fn cancellation_penalty)]
+	pub type CancellationPenalty<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Orders which are renewed for the next bulk period after each fulfilment.
+	///
+	/// Recurring orders keep going until the order funds no longer cover the reward or the
+	/// creator cancels the order.
+	#[pallet::storage]
+	#[pallet::getter(fn recurring_orders)]
+	pub type RecurringOrders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, ()>;
+
+	/// The sum of all contributions made to an order.
+	#[pallet::storage]
+	#[pallet::getter(fn total_contributions)]
+	pub type TotalContributions<T: Config> =
+		StorageMap<_, Blake2_128Concat, OrderId, BalanceOf<T>, ValueQuery>;
+
+	/// Cancelled orders whose contributors have not all been refunded yet, mapped to the order
+	/// creator.
+	///
+	/// The refunds are processed with the spare block weight. Once all contributors are refunded,
+	/// the rest of the order funds go to the creator.
+	#[pallet::storage]
+	#[pallet::getter(fn cancelled_orders)]
+	pub type CancelledOrders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, T::AccountId>;
+
+	/// Contributors of cancelled orders whose automatic refund failed.
+	///
+	/// These contributors are skipped when refunding the order, and claim back their
+	/// contributions themselves. The creator doesn't get the rest of the order funds in that case.
+	#[pallet::storage]
+	#[pallet::getter(fn failed_refunds)]
+	pub type FailedRefunds<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, OrderId, Blake2_128Concat, T::AccountId, ()>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -133,6 +274,44 @@ pub mod pallet {
 		Contributed { order_id: OrderId, who: T::AccountId, amount: BalanceOf<T> },
 		/// A contribution was removed from the cancelled order.
 		ContributionRemoved { order_id: OrderId, who: T::AccountId, amount: BalanceOf<T> },
+		/// A recurring order was created.
+		RecurringOrderCreated { order_id: OrderId, reward: BalanceOf<T> },
+		/// A recurring order got renewed for the next period.
+		OrderRenewed { order_id: OrderId, requirements: Requirements },
+		/// The order cancellation penalty got updated.
+		CancellationPenaltyUpdated { penalty: BalanceOf<T> },
+		/// The requirements of an order got updated by its creator.
+		OrderUpdated { order_id: OrderId, requirements: Requirements },
+		/// The funding target of an order got updated by its creator.
+		FundingTargetSet { order_id: OrderId, funding_target: Option<BalanceOf<T>> },
+		/// The contributions made to an order reached its funding target.
+		OrderFullyFunded { order_id: OrderId, total: BalanceOf<T> },
+		/// A contribution was made to the order in an asset other than `Currency`.
+		AssetContributed {
+			order_id: OrderId,
+			who: T::AccountId,
+			asset: T::AssetId,
+			amount: BalanceOf<T>,
+		},
+		/// A contribution in an asset other than `Currency` was removed from the order.
+		AssetContributionRemoved {
+			order_id: OrderId,
+			who: T::AccountId,
+			asset: T::AssetId,
+			amount: BalanceOf<T>,
+		},
+		/// All contributors of a cancelled order got refunded and the order storage got cleared.
+		OrderRefunded { order_id: OrderId },
+		/// Refunding a contributor of a cancelled order failed. The contributor can still claim
+		/// back their contribution.
+		RefundFailed { order_id: OrderId, who: T::AccountId },
+		/// A contribution, or part of it, was transferred to another account.
+		ContributionTransferred {
+			order_id: OrderId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -150,6 +329,31 @@ pub mod pallet {
 		OrderNotCancelled,
 		/// The contributed amount equals to zero.
 		NoContribution,
+		/// The maximum reward of an order must be greater than zero.
+		InvalidReward,
+		/// The requirements of an order can only become stricter once it has contributions.
+		OrderHasContributions,
+		/// The order period must begin before it ends, and must not end in the past.
+		InvalidPeriod,
+		/// Orders cannot be funded with the given asset.
+		UnsupportedAsset,
+		/// The order is already funded with the maximum number of assets.
+		TooManyAssets,
+		/// The XCM origin is not a sibling parachain.
+		InvalidLocation,
+		/// The transferred amount exceeds the contribution of the caller.
+		InsufficientContribution,
+		/// Contributions of accounts that also contributed in other assets cannot be transferred.
+		HasAssetContributions,
+		/// A contribution cannot be transferred to the account it belongs to.
+		SelfTransfer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::refund_cancelled_orders(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -159,16 +363,19 @@ pub mod pallet {
 		/// ## Arguments:
 		/// - `para_id`: The para id to which Coretime will be allocated.
 		/// - `requirements`: Region requirements of the order.
+		/// - `max_reward`: The maximum reward paid out for fulfilling the order. Contributions
+		///   exceeding it can be claimed back once the order is fulfilled.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::create_order())]
 		pub fn create_order(
 			origin: OriginFor<T>,
 			para_id: ParaId,
 			requirements: Requirements,
+			max_reward: Option<BalanceOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			Self::do_create_order(who, para_id, requirements)
+			Self::do_create_order(who, para_id, requirements, max_reward)
 		}
 
 		/// Extrinsic for cancelling an order.
@@ -176,7 +383,12 @@ pub mod pallet {
 		/// If the region requirements on which the order was based are for an expired region,
 		/// anyone can cancel the order.
 		///
-		/// Only expired orders can be cancelled.
+		/// Besides that, the creator can cancel an unfulfilled order at any time. Cancelling an
+		/// order before it expires incurs the cancellation penalty, paid by the creator.
+		///
+		/// Once the order is cancelled contributors can claim back their funds through
+		/// `remove_contribution`. Otherwise, they get refunded automatically once there is spare
+		/// block weight.
 		///
 		/// ## Arguments:
 		/// - `order_id`: The order the caller wants to cancel.
@@ -185,7 +397,7 @@ pub mod pallet {
 		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			Self::do_cancel_order(order_id, Self::current_timeslice())?;
+			Self::do_cancel_order(order_id, &who, Self::current_timeslice())?;
 			Self::deposit_event(Event::OrderRemoved { order_id, by: who });
 
 			Ok(())
@@ -205,54 +417,272 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			Self::do_contribute(order_id, who, amount)
+		}
+
+		/// Extrinsic for removing contributions from a cancelled or fulfilled order.
+		///
+		/// If rewards were already paid out from the order, the remaining funds are shared among
+		/// the contributors pro rata to their contributions.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The cancelled order from which the user wants to claim back their
+		///   contribution.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::remove_contribution())]
+		pub fn remove_contribution(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Orders::<T>::get(order_id).is_none(), Error::<T>::OrderNotCancelled);
+
+			Self::do_remove_contribution(order_id, who)
+		}
+
+		/// Extrinsic for creating a recurring order.
+		///
+		/// After each fulfilment the requirements of a recurring order get rolled forward by a
+		/// bulk period, while the remaining contributions stay with the order. The order stops
+		/// once its funds no longer cover the reward, or when the creator cancels it.
+		///
+		/// ## Arguments:
+		/// - `para_id`: The para id to which Coretime will be allocated.
+		/// - `requirements`: Region requirements of the first period.
+		/// - `reward`: The reward paid out for each fulfilment.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::create_recurring_order())]
+		pub fn create_recurring_order(
+			origin: OriginFor<T>,
+			para_id: ParaId,
+			requirements: Requirements,
+			reward: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order_id = NextOrderId::<T>::get();
+			Self::do_create_order(who, para_id, requirements, Some(reward))?;
+			RecurringOrders::<T>::insert(order_id, ());
+
+			Self::deposit_event(Event::RecurringOrderCreated { order_id, reward });
+
+			Ok(())
+		}
+
+		/// Extrinsic for updating the order cancellation penalty.
+		///
+		/// ## Arguments:
+		/// - `penalty`: The amount the creator pays for cancelling an order before it expires.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_cancellation_penalty())]
+		pub fn set_cancellation_penalty(
+			origin: OriginFor<T>,
+			penalty: BalanceOf<T>,
+		) -> DispatchResult {
+			T::CancellationPenaltyOrigin::ensure_origin(origin)?;
+
+			CancellationPenalty::<T>::put(penalty);
+
+			Self::deposit_event(Event::CancellationPenaltyUpdated { penalty });
+			Ok(())
+		}
+
+		/// Extrinsic for updating the requirements of an unfulfilled order.
+		///
+		/// Only callable by the order creator. Once the order has contributions its period is
+		/// fixed, and the core occupancy can only be raised.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The order to update.
+		/// - `requirements`: The new region requirements of the order.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::update_order())]
+		pub fn update_order(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			requirements: Requirements,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
+			ensure!(order.creator == who, Error::<T>::NotAllowed);
+
+			ensure!(requirements.begin < requirements.end, Error::<T>::InvalidPeriod);
+			ensure!(Self::current_timeslice() < requirements.end, Error::<T>::InvalidPeriod);
+
+			if !TotalContributions::<T>::get(order_id).is_zero() {
+				ensure!(
+					order.requirements.begin == requirements.begin &&
+						order.requirements.end == requirements.end &&
+						order.requirements.core_occupancy <= requirements.core_occupancy,
+					Error::<T>::OrderHasContributions
+				);
+			}
+
+			order.requirements = requirements.clone();
+			Orders::<T>::insert(order_id, order);
+
+			Self::deposit_event(Event::OrderUpdated { order_id, requirements });
+			Ok(())
+		}
+
+		/// Extrinsic for setting the funding target of an order.
+		///
+		/// Only callable by the order creator. An `OrderFullyFunded` event is emitted once the
+		/// contributions reach the target.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The order to set the funding target for.
+		/// - `funding_target`: The amount the order aims to raise. `None` removes the target.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_funding_target())]
+		pub fn set_funding_target(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			funding_target: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
+			ensure!(order.creator == who, Error::<T>::NotAllowed);
+
+			order.funding_target = funding_target;
+			Orders::<T>::insert(order_id, order);
+
+			Self::deposit_event(Event::FundingTargetSet { order_id, funding_target });
+
+			let total = TotalContributions::<T>::get(order_id);
+			if funding_target.map_or(false, |target| total >= target) {
+				Self::deposit_event(Event::OrderFullyFunded { order_id, total });
+			}
+
+			Ok(())
+		}
+
+		/// Extrinsic for contributing to an order in an asset other than `Currency`.
+		///
+		/// Contributions in other assets are paid out to the fulfiller in full, on top of the
+		/// reward. They are not counted towards the funding target, and recurring orders cannot
+		/// be funded with them.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The order to which the caller wants to contribute.
+		/// - `asset`: The asset the caller contributes in.
+		/// - `amount`: The amount of the asset the caller wants to contribute.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::contribute_asset())]
+		pub fn contribute_asset(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			asset: T::AssetId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::SupportedAssets::contains(&asset), Error::<T>::UnsupportedAsset);
+
 			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
 			ensure!(Self::current_timeslice() < order.requirements.end, Error::<T>::OrderExpired);
+			ensure!(!RecurringOrders::<T>::contains_key(order_id), Error::<T>::NotAllowed);
+
+			ensure!(
+				!amount.is_zero() && amount >= T::Assets::minimum_balance(asset),
+				Error::<T>::InvalidAmount
+			);
+
+			TotalAssetContributions::<T>::try_mutate(order_id, |totals| -> DispatchResult {
+				match totals.iter_mut().find(|(id, _)| *id == asset) {
+					Some((_, total)) => *total = total.saturating_add(amount),
+					None =>
+						totals.try_push((asset, amount)).map_err(|_| Error::<T>::TooManyAssets)?,
+				}
+				Ok(())
+			})?;
 
-			ensure!(amount >= T::MinimumContribution::get(), Error::<T>::InvalidAmount);
 			let order_account = T::OrderToAccountId::convert(order_id);
-			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
-				&who,
-				&order_account,
-				amount,
-				ExistenceRequirement::KeepAlive,
-			)?;
+			T::Assets::transfer(asset, &who, &order_account, amount)?;
 
-			let mut contribution: BalanceOf<T> = Contributions::<T>::get(order_id, who.clone());
-			contribution = contribution.saturating_add(amount);
-			Contributions::<T>::insert(order_id, who.clone(), contribution);
+			AssetContributions::<T>::mutate((order_id, who.clone(), asset), |contribution| {
+				*contribution = contribution.saturating_add(amount)
+			});
 
-			Self::deposit_event(Event::Contributed { order_id, who, amount });
+			Self::deposit_event(Event::AssetContributed { order_id, who, asset, amount });
 
 			Ok(())
 		}
 
-		/// Extrinsic for removing contributions from a cancelled order.
+		/// Extrinsic for removing a contribution made in an asset other than `Currency` from a
+		/// cancelled or fulfilled order.
 		///
 		/// ## Arguments:
-		/// - `order_id`: The cancelled order from which the user wants to claim back their
-		///   contribution.
-		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::remove_contribution())]
-		pub fn remove_contribution(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+		/// - `order_id`: The order from which the user wants to claim back their contribution.
+		/// - `asset`: The asset of the contribution.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::remove_asset_contribution())]
+		pub fn remove_asset_contribution(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			asset: T::AssetId,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			ensure!(Orders::<T>::get(order_id).is_none(), Error::<T>::OrderNotCancelled);
 
-			let amount: BalanceOf<T> = Contributions::<T>::get(order_id, who.clone());
-			ensure!(amount != Default::default(), Error::<T>::NoContribution);
+			Self::do_remove_asset_contribution(order_id, who, asset)
+		}
 
-			let order_account = T::OrderToAccountId::convert(order_id);
-			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
-				&order_account,
-				&who,
-				amount,
-				ExistenceRequirement::AllowDeath,
-			)?;
-			Contributions::<T>::remove(order_id, who.clone());
+		/// Extrinsic for creating and funding an order from a sibling parachain via XCM.
+		///
+		/// The order is created for the sibling parachain, with its sovereign account as the
+		/// creator. The creation fee and the contribution are paid from the relay chain assets
+		/// held by the sovereign account, which therefore has to be funded beforehand, e.g. by a
+		/// separate reserve transfer.
+		///
+		/// ## Arguments:
+		/// - `requirements`: Region requirements of the order.
+		/// - `contribution`: The amount the sibling parachain contributes to the order.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::create_order_via_xcm())]
+		pub fn create_order_via_xcm(
+			origin: OriginFor<T>,
+			requirements: Requirements,
+			contribution: BalanceOf<T>,
+		) -> DispatchResult {
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			let MultiLocation { parents: 1, interior: Junctions::X1(Junction::Parachain(para_id)) } =
+				location
+			else {
+				return Err(Error::<T>::InvalidLocation.into())
+			};
+			let who = T::LocationToAccountId::convert_location(&location)
+				.ok_or(Error::<T>::InvalidLocation)?;
 
-			Self::deposit_event(Event::ContributionRemoved { who, order_id, amount });
+			let order_id = NextOrderId::<T>::get();
+			Self::do_create_order(who.clone(), para_id.into(), requirements, None)?;
+			Self::do_contribute(order_id, who, contribution)
+		}
 
-			Ok(())
+		/// Extrinsic for transferring a contribution, or part of it, to another account.
+		///
+		/// The receiver takes over the transferred position, so any refund of the order funds is
+		/// paid out to them. Contributions can only be transferred while the order is open;
+		/// once it is fulfilled or cancelled the positions are final.
+		///
+		/// Neither side can be left with a contribution below `MinimumContribution`, and
+		/// contributions of accounts that also contributed in other assets cannot be transferred.
+		///
+		/// ## Arguments:
+		/// - `order_id`: The order the contribution was made to.
+		/// - `to`: The account receiving the contribution.
+		/// - `amount`: The part of the contribution to transfer.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::transfer_contribution())]
+		pub fn transfer_contribution(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transfer_contribution(order_id, who, to, amount)
 		}
 	}
 
@@ -261,22 +691,111 @@ pub mod pallet {
 			creator: T::AccountId,
 			para_id: ParaId,
 			requirements: Requirements,
+			max_reward: Option<BalanceOf<T>>,
 		) -> DispatchResult {
+			if let Some(reward) = max_reward {
+				ensure!(!reward.is_zero(), Error::<T>::InvalidReward);
+			}
 			T::OrderCreationFeeHandler::handle(&creator, T::OrderCreationCost::get())?;
 
 			let order_id = NextOrderId::<T>::get();
 			Orders::<T>::insert(
 				order_id,
-				Order { creator: creator.clone(), para_id, requirements },
+				Order {
+					creator: creator.clone(),
+					para_id,
+					requirements,
+					max_reward,
+					funding_target: None,
+				},
 			);
+			ParaOrders::<T>::insert(para_id, order_id, ());
+			CreatorOrders::<T>::insert(creator.clone(), order_id, ());
 			NextOrderId::<T>::put(order_id.saturating_add(1));
 
 			Self::deposit_event(Event::OrderCreated { order_id, by: creator });
 			Ok(())
 		}
 
+		pub(crate) fn do_contribute(
+			order_id: OrderId,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
+			ensure!(Self::current_timeslice() < order.requirements.end, Error::<T>::OrderExpired);
+
+			ensure!(amount >= T::MinimumContribution::get(), Error::<T>::InvalidAmount);
+			let order_account = T::OrderToAccountId::convert(order_id);
+			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
+				&who,
+				&order_account,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let mut contribution: BalanceOf<T> = Contributions::<T>::get(order_id, who.clone());
+			contribution = contribution.saturating_add(amount);
+			Contributions::<T>::insert(order_id, who.clone(), contribution);
+			let total = TotalContributions::<T>::mutate(order_id, |total| {
+				*total = total.saturating_add(amount);
+				*total
+			});
+
+			Self::deposit_event(Event::Contributed { order_id, who, amount });
+
+			if let Some(target) = order.funding_target {
+				if total >= target && total.saturating_sub(amount) < target {
+					Self::deposit_event(Event::OrderFullyFunded { order_id, total });
+				}
+			}
+
+			Ok(())
+		}
+
+		pub(crate) fn do_transfer_contribution(
+			order_id: OrderId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(Orders::<T>::contains_key(order_id), Error::<T>::InvalidOrderId);
+			ensure!(from != to, Error::<T>::SelfTransfer);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			let contribution: BalanceOf<T> = Contributions::<T>::get(order_id, from.clone());
+			ensure!(amount <= contribution, Error::<T>::InsufficientContribution);
+			ensure!(
+				AssetContributions::<T>::iter_key_prefix((order_id, from.clone()))
+					.next()
+					.is_none(),
+				Error::<T>::HasAssetContributions
+			);
+
+			// Neither side can be left with less than the minimum contribution.
+			let remaining = contribution.saturating_sub(amount);
+			let received = Contributions::<T>::get(order_id, to.clone()).saturating_add(amount);
+			ensure!(
+				(remaining.is_zero() || remaining >= T::MinimumContribution::get()) &&
+					received >= T::MinimumContribution::get(),
+				Error::<T>::InvalidAmount
+			);
+
+			if remaining.is_zero() {
+				Contributions::<T>::remove(order_id, from.clone());
+			} else {
+				Contributions::<T>::insert(order_id, from.clone(), remaining);
+			}
+			Contributions::<T>::insert(order_id, to.clone(), received);
+
+			Self::deposit_event(Event::ContributionTransferred { order_id, from, to, amount });
+
+			Ok(())
+		}
+
 		pub(crate) fn do_cancel_order(
 			order_id: OrderId,
+			who: &T::AccountId,
 			current_timeslice: Timeslice,
 		) -> DispatchResult {
 			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
@@ -285,13 +804,271 @@ pub mod pallet {
 			// benchmarks much easier. With this we can set the start and end to 0 and be able to
 			// cancel the order without having to modify the current timeslice.
 			#[cfg(feature = "runtime-benchmarks")]
-			ensure!(order.requirements.end <= current_timeslice, Error::<T>::NotAllowed);
+			let expired = order.requirements.end <= current_timeslice;
 			#[cfg(not(feature = "runtime-benchmarks"))]
-			ensure!(order.requirements.end < current_timeslice, Error::<T>::NotAllowed);
+			let expired = order.requirements.end < current_timeslice;
+
+			if !expired {
+				// Only the creator can cancel the order early, in which case they pay a penalty.
+				ensure!(*who == order.creator, Error::<T>::NotAllowed);
+
+				let penalty = CancellationPenalty::<T>::get();
+				if !penalty.is_zero() {
+					T::CancellationPenaltyHandler::handle(&order.creator, penalty)?;
+				}
+			}
+
+			Self::do_remove_order(order_id, &order);
+			RecurringOrders::<T>::remove(order_id);
+			CancelledOrders::<T>::insert(order_id, order.creator);
+			Ok(())
+		}
+
+		/// Removes the order together with its index entries.
+		pub(crate) fn do_remove_order(order_id: OrderId, order: &OrderOf<T>) {
 			Orders::<T>::remove(order_id);
+			ParaOrders::<T>::remove(order.para_id, order_id);
+			CreatorOrders::<T>::remove(&order.creator, order_id);
+		}
+
+		/// Returns up to `limit` open orders, starting after `start_after`.
+		///
+		/// Orders are returned in storage order, so the last returned order can be used as
+		/// `start_after` for getting the next page.
+		pub fn open_orders(start_after: Option<OrderId>, limit: u32) -> Vec<(OrderId, OrderOf<T>)> {
+			let orders = match start_after {
+				Some(last) => Orders::<T>::iter_from(Orders::<T>::hashed_key_for(last)),
+				None => Orders::<T>::iter(),
+			};
+
+			let current_timeslice = Self::current_timeslice();
+			orders
+				.filter(|(_, order)| current_timeslice < order.requirements.end)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// Returns the open orders created for `para_id`.
+		pub fn orders_of_para(para_id: ParaId) -> Vec<(OrderId, OrderOf<T>)> {
+			Self::open_orders_of(ParaOrders::<T>::iter_key_prefix(para_id))
+		}
+
+		/// Returns the open orders created by `creator`.
+		pub fn orders_of_creator(creator: &T::AccountId) -> Vec<(OrderId, OrderOf<T>)> {
+			Self::open_orders_of(CreatorOrders::<T>::iter_key_prefix(creator))
+		}
+
+		fn open_orders_of(order_ids: impl Iterator<Item = OrderId>) -> Vec<(OrderId, OrderOf<T>)> {
+			let current_timeslice = Self::current_timeslice();
+			order_ids
+				.filter_map(|order_id| Orders::<T>::get(order_id).map(|order| (order_id, order)))
+				.filter(|(_, order)| current_timeslice < order.requirements.end)
+				.collect()
+		}
+
+		/// Refunds the contributors of cancelled orders until `weight_limit` is reached.
+		///
+		/// Once all contributors of an order are refunded, the order gets cleaned up. Returns the
+		/// consumed weight.
+		pub(crate) fn refund_cancelled_orders(weight_limit: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(weight_limit);
+
+			// Reading the next cancelled order.
+			let read_weight = T::DbWeight::get().reads(1);
+			// Reading the next contribution and refunding it.
+			let refund_weight = read_weight.saturating_add(
+				T::WeightInfo::remove_contribution()
+					.max(T::WeightInfo::remove_asset_contribution()),
+			);
+			let cleanup_weight = T::WeightInfo::clean_up_order();
+
+			while meter.try_consume(read_weight).is_ok() {
+				let Some((order_id, creator)) = CancelledOrders::<T>::iter().next() else { break };
+
+				// Contributors whose refund failed are skipped so that the refunds don't get stuck.
+				let mut skipped = 0u64;
+				let mut refundable = |who: &T::AccountId| {
+					let failed = FailedRefunds::<T>::contains_key(order_id, who);
+					skipped.saturating_accrue(failed.into());
+					!failed
+				};
+				let contributor = Contributions::<T>::iter_key_prefix(order_id)
+					.find(&mut refundable)
+					.map(|who| (who, None))
+					.or_else(|| {
+						AssetContributions::<T>::iter_key_prefix((order_id,))
+							.find(|(who, _)| refundable(who))
+							.map(|(who, asset)| (who, Some(asset)))
+					});
+				meter.consume(T::DbWeight::get().reads(skipped.saturating_mul(2)));
+
+				let Some((who, asset)) = contributor else {
+					if meter.try_consume(cleanup_weight).is_err() {
+						break
+					}
+					if FailedRefunds::<T>::iter_key_prefix(order_id).next().is_some() {
+						// The funds of the skipped contributors stay in the order account until
+						// they claim them.
+						let _ = FailedRefunds::<T>::clear_prefix(order_id, u32::MAX, None);
+						CancelledOrders::<T>::remove(order_id);
+					} else {
+						Self::clean_up_order(order_id, &creator);
+					}
+					continue
+				};
+
+				if meter.try_consume(refund_weight).is_err() {
+					break
+				}
+				let refund = with_storage_layer::<_, DispatchError, _>(|| match asset {
+					Some(asset) => Self::do_remove_asset_contribution(order_id, who.clone(), asset),
+					None => Self::do_remove_contribution(order_id, who.clone()),
+				});
+				if let Err(err) = refund {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to refund {:?} from order {:?} in asset {:?}: {:?}",
+						who,
+						order_id,
+						asset,
+						err
+					);
+					FailedRefunds::<T>::insert(order_id, &who, ());
+					Self::deposit_event(Event::RefundFailed { order_id, who });
+				}
+			}
+
+			meter.consumed()
+		}
+
+		/// Clears the storage of a cancelled order whose contributors were all refunded.
+		///
+		/// Anything left in the order account is transferred to the creator, or burned if it is
+		/// too little to be transferred, so that the account gets reaped.
+		pub(crate) fn clean_up_order(order_id: OrderId, creator: &T::AccountId) {
+			let order_account = T::OrderToAccountId::convert(order_id);
+
+			for (asset, _) in TotalAssetContributions::<T>::take(order_id) {
+				let dust = T::Assets::free_balance(asset, &order_account);
+				if !dust.is_zero() &&
+					T::Assets::transfer(asset, &order_account, creator, dust).is_err()
+				{
+					let _ = T::Assets::withdraw(asset, &order_account, dust);
+				}
+			}
+
+			let dust = T::Currency::free_balance(&order_account);
+			if !dust.is_zero() &&
+				<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
+					&order_account,
+					creator,
+					dust,
+					ExistenceRequirement::AllowDeath,
+				)
+				.is_err()
+			{
+				let _ = <<T as Config>::Currency as Currency<T::AccountId>>::slash(
+					&order_account,
+					dust,
+				);
+			}
+
+			TotalContributions::<T>::remove(order_id);
+			CancelledOrders::<T>::remove(order_id);
+
+			Self::deposit_event(Event::OrderRefunded { order_id });
+		}
+
+		pub(crate) fn do_remove_contribution(
+			order_id: OrderId,
+			who: T::AccountId,
+		) -> DispatchResult {
+			let contribution: BalanceOf<T> = Contributions::<T>::get(order_id, who.clone());
+			ensure!(contribution != Default::default(), Error::<T>::NoContribution);
+
+			let order_account = T::OrderToAccountId::convert(order_id);
+			let amount = Self::refund_amount(
+				contribution,
+				TotalContributions::<T>::get(order_id),
+				T::Currency::free_balance(&order_account),
+			);
+
+			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
+				&order_account,
+				&who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Contributions::<T>::remove(order_id, who.clone());
+			TotalContributions::<T>::mutate_exists(order_id, |maybe_total| {
+				let remaining = maybe_total.unwrap_or_default().saturating_sub(contribution);
+				*maybe_total = (!remaining.is_zero()).then_some(remaining);
+			});
+
+			Self::deposit_event(Event::ContributionRemoved { who, order_id, amount });
+
 			Ok(())
 		}
 
+		pub(crate) fn do_remove_asset_contribution(
+			order_id: OrderId,
+			who: T::AccountId,
+			asset: T::AssetId,
+		) -> DispatchResult {
+			let contribution = AssetContributions::<T>::get((order_id, who.clone(), asset));
+			ensure!(!contribution.is_zero(), Error::<T>::NoContribution);
+
+			let mut totals = TotalAssetContributions::<T>::get(order_id);
+			let total = totals.iter().find(|(id, _)| *id == asset).map_or(Zero::zero(), |t| t.1);
+
+			let order_account = T::OrderToAccountId::convert(order_id);
+			let amount = Self::refund_amount(
+				contribution,
+				total,
+				T::Assets::free_balance(asset, &order_account),
+			);
+
+			if !amount.is_zero() {
+				T::Assets::transfer(asset, &order_account, &who, amount)?;
+			}
+			AssetContributions::<T>::remove((order_id, who.clone(), asset));
+
+			totals.iter_mut().for_each(|(id, total)| {
+				if *id == asset {
+					*total = total.saturating_sub(contribution);
+				}
+			});
+			totals.retain(|(_, total)| !total.is_zero());
+			if totals.is_empty() {
+				TotalAssetContributions::<T>::remove(order_id);
+			} else {
+				TotalAssetContributions::<T>::insert(order_id, totals);
+			}
+
+			Self::deposit_event(Event::AssetContributionRemoved { order_id, who, asset, amount });
+
+			Ok(())
+		}
+
+		/// Returns the amount a contributor gets back out of the remaining order `funds`.
+		///
+		/// If rewards were already paid out from the order, the contributors share the remaining
+		/// funds pro rata to their contributions.
+		pub(crate) fn refund_amount(
+			contribution: BalanceOf<T>,
+			total: BalanceOf<T>,
+			funds: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			if funds >= total {
+				contribution
+			} else if contribution >= total {
+				// The last contributor gets the remaining funds.
+				funds
+			} else {
+				Perbill::from_rational(contribution, total) * funds
+			}
+		}
+
 		pub(crate) fn current_timeslice() -> Timeslice {
 			let latest_rc_block = T::RCBlockNumberProvider::current_block_number();
 			let timeslice_period = T::TimeslicePeriod::get();
@@ -299,15 +1076,73 @@ pub mod pallet {
 		}
 	}
 
-	impl<T: Config> OrderInspect<T::AccountId> for Pallet<T> {
-		fn order(order_id: &OrderId) -> Option<Order<T::AccountId>> {
+	impl<T: Config> OrderInspect<T::AccountId, BalanceOf<T>> for Pallet<T> {
+		fn order(order_id: &OrderId) -> Option<OrderOf<T>> {
 			Orders::<T>::get(order_id)
 		}
 
 		fn remove_order(order_id: &OrderId) {
-			Orders::<T>::remove(order_id)
+			if let Some(order) = Orders::<T>::get(order_id) {
+				Self::do_remove_order(*order_id, &order);
+			}
+		}
+	}
+}
+
+impl<T: crate::Config> OrderFulfillment<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn reward(order_id: &OrderId, available: BalanceOf<T>) -> BalanceOf<T> {
+		match Orders::<T>::get(order_id).and_then(|order| order.max_reward) {
+			Some(max_reward) => max_reward.min(available),
+			None => available,
 		}
 	}
+
+	fn pay_asset_rewards(order_id: &OrderId, recipients: &[(T::AccountId, u32)]) {
+		let Some(((last, _), others)) = recipients.split_last() else { return };
+		let total_weight =
+			recipients.iter().fold(0u32, |total, (_, weight)| total.saturating_add(*weight));
+
+		let order_account = T::OrderToAccountId::convert(*order_id);
+		for (asset, _) in TotalAssetContributions::<T>::get(order_id) {
+			let funds = T::Assets::free_balance(asset, &order_account);
+			let mut remaining = funds;
+			for (recipient, weight) in others {
+				let share = Perbill::from_rational(*weight, total_weight).mul_floor(funds);
+				if T::Assets::transfer(asset, &order_account, recipient, share).is_ok() {
+					remaining = remaining.saturating_sub(share);
+				}
+			}
+			// The last recipient gets the remainder so that no dust is left over.
+			let _ = T::Assets::transfer(asset, &order_account, last, remaining);
+		}
+	}
+
+	fn on_fulfilled(order_id: &OrderId) {
+		let Some(mut order) = Orders::<T>::get(order_id) else { return };
+		if !RecurringOrders::<T>::contains_key(order_id) {
+			Self::do_remove_order(*order_id, &order);
+			return
+		}
+
+		// Once the funds run out the order stops, and the contributors can claim back what is
+		// left.
+		let order_account = T::OrderToAccountId::convert(*order_id);
+		if T::Currency::free_balance(&order_account) < order.max_reward.unwrap_or_default() {
+			Self::do_remove_order(*order_id, &order);
+			RecurringOrders::<T>::remove(order_id);
+			return
+		}
+
+		let period = T::BulkPeriod::get();
+		order.requirements.begin = order.requirements.begin.saturating_add(period);
+		order.requirements.end = order.requirements.end.saturating_add(period);
+		Orders::<T>::insert(order_id, &order);
+
+		Self::deposit_event(Event::OrderRenewed {
+			order_id: *order_id,
+			requirements: order.requirements,
+		});
+	}
 }
 
 impl<T: crate::Config> OrderFactory<T::AccountId> for Pallet<T> {
@@ -316,7 +1151,7 @@ impl<T: crate::Config> OrderFactory<T::AccountId> for Pallet<T> {
 		para_id: ParaId,
 		requirements: Requirements,
 	) -> sp_runtime::DispatchResult {
-		crate::Pallet::<T>::do_create_order(creator, para_id, requirements)?;
+		crate::Pallet::<T>::do_create_order(creator, para_id, requirements, None)?;
 		Ok(())
 	}
 }
@@ -0,0 +1,279 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations of the orders pallet.
+//!
+//! Each version corresponds to a change of the order layout or of the order indexes. The
+//! migrations have to be applied in order.
+
+use crate::*;
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+	migrations::VersionedMigration,
+	pallet_prelude::{OptionQuery, Weight},
+	storage_alias,
+	traits::OnRuntimeUpgrade,
+	Blake2_128Concat,
+};
+
+/// Returns the number of orders, to be checked after the migration.
+#[cfg(feature = "try-runtime")]
+fn order_count<T: Config>() -> Vec<u8> {
+	(Orders::<T>::iter_keys().count() as u32).encode()
+}
+
+/// Ensures no order got lost in the migration.
+#[cfg(feature = "try-runtime")]
+fn ensure_order_count(state: Vec<u8>, current: usize) -> Result<(), sp_runtime::TryRuntimeError> {
+	let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre upgrade state")?;
+	ensure!(current as u32 == count, "Orders got lost in the migration");
+	Ok(())
+}
+
+pub mod v0 {
+	use super::*;
+
+	/// The initial order layout.
+	#[derive(Encode, Decode)]
+	pub struct Order<AccountId> {
+		pub creator: AccountId,
+		pub para_id: ParaId,
+		pub requirements: Requirements,
+	}
+
+	#[storage_alias]
+	pub type Orders<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		OrderId,
+		Order<<T as frame_system::Config>::AccountId>,
+		OptionQuery,
+	>;
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Recurring orders along with the reward paid out for each fulfilment.
+	#[storage_alias]
+	pub type RecurringOrders<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, OrderId, BalanceOf<T>, OptionQuery>;
+
+	/// Sums up the contributions made to each order.
+	pub struct VersionUncheckedMigrateToV1<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut contributions = 0u64;
+			for (order_id, _, amount) in Contributions::<T>::iter() {
+				contributions.saturating_inc();
+				TotalContributions::<T>::mutate(order_id, |total| total.saturating_accrue(amount));
+			}
+
+			log::info!(target: LOG_TARGET, "Summed up {} contributions", contributions);
+			// Reading the contribution and the total, writing the total.
+			T::DbWeight::get().reads_writes(contributions.saturating_mul(2), contributions)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				Orders::<T>::iter_keys().all(|order_id| {
+					let total = Contributions::<T>::iter_prefix_values(order_id)
+						.fold(BalanceOf::<T>::zero(), |total, amount| total.saturating_add(amount));
+					TotalContributions::<T>::get(order_id) == total
+				}),
+				"Total contributions don't match the contributions"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrates the orders storage from version 0 to 1.
+	pub type MigrateToV1<T> = VersionedMigration<
+		0,
+		1,
+		VersionUncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The order layout once orders got a maximum reward.
+	#[derive(Encode, Decode)]
+	pub struct Order<AccountId, Balance> {
+		pub creator: AccountId,
+		pub para_id: ParaId,
+		pub requirements: Requirements,
+		pub max_reward: Option<Balance>,
+	}
+
+	#[storage_alias]
+	pub type Orders<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		OrderId,
+		Order<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Adds the maximum reward to the orders.
+	///
+	/// The reward of recurring orders moves from the recurring orders to the order itself. Other
+	/// orders keep paying out all of their funds as the reward.
+	pub struct VersionUncheckedMigrateToV2<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Orders::<T>::translate::<v0::Order<T::AccountId>, _>(|order_id, old| {
+				migrated.saturating_inc();
+				Some(Order {
+					creator: old.creator,
+					para_id: old.para_id,
+					requirements: old.requirements,
+					max_reward: v1::RecurringOrders::<T>::get(order_id),
+				})
+			});
+
+			let mut recurring = 0u64;
+			RecurringOrders::<T>::translate::<BalanceOf<T>, _>(|_, _| {
+				recurring.saturating_inc();
+				Some(())
+			});
+
+			log::info!(target: LOG_TARGET, "Migrated {} orders to v2", migrated);
+			// Reading the order and the reward, writing the order and the recurring order.
+			T::DbWeight::get().reads_writes(
+				migrated.saturating_mul(2).saturating_add(recurring),
+				migrated.saturating_add(recurring),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(order_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_order_count(state, Orders::<T>::iter_values().count())?;
+			ensure!(
+				RecurringOrders::<T>::iter_keys().all(|order_id| {
+					Orders::<T>::get(order_id).map_or(true, |order| order.max_reward.is_some())
+				}),
+				"Recurring orders lost their reward"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrates the orders storage from version 1 to 2.
+	pub type MigrateToV2<T> = VersionedMigration<
+		1,
+		2,
+		VersionUncheckedMigrateToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Adds the funding target to the orders. The migrated orders have no funding target.
+	pub struct VersionUncheckedMigrateToV3<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = 0u64;
+			Orders::<T>::translate::<v2::Order<T::AccountId, BalanceOf<T>>, _>(|_, old| {
+				migrated.saturating_inc();
+				Some(Order {
+					creator: old.creator,
+					para_id: old.para_id,
+					requirements: old.requirements,
+					max_reward: old.max_reward,
+					funding_target: None,
+				})
+			});
+
+			log::info!(target: LOG_TARGET, "Migrated {} orders to v3", migrated);
+			T::DbWeight::get().reads_writes(migrated, migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(order_count::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure_order_count(state, Orders::<T>::iter_values().count())
+		}
+	}
+
+	/// Migrates the orders storage from version 2 to 3.
+	pub type MigrateToV3<T> = VersionedMigration<
+		2,
+		3,
+		VersionUncheckedMigrateToV3<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Indexes the orders by parachain and by creator.
+	pub struct VersionUncheckedMigrateToV4<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for VersionUncheckedMigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut indexed = 0u64;
+			for (order_id, order) in Orders::<T>::iter() {
+				indexed.saturating_inc();
+				ParaOrders::<T>::insert(order.para_id, order_id, ());
+				CreatorOrders::<T>::insert(order.creator, order_id, ());
+			}
+
+			log::info!(target: LOG_TARGET, "Indexed {} orders", indexed);
+			T::DbWeight::get().reads_writes(indexed, indexed.saturating_mul(2))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				Orders::<T>::iter().all(|(order_id, order)| {
+					ParaOrders::<T>::contains_key(order.para_id, order_id) &&
+						CreatorOrders::<T>::contains_key(&order.creator, order_id)
+				}),
+				"Orders are missing from the indexes"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrates the orders storage from version 3 to 4.
+	pub type MigrateToV4<T> = VersionedMigration<
+		3,
+		4,
+		VersionUncheckedMigrateToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
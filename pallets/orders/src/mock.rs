@@ -17,18 +17,24 @@ use crate::{FeeHandler, OrderId};
 use frame_support::{
 	pallet_prelude::*,
 	parameter_types,
-	traits::{fungible::Mutate, tokens::Preservation, Everything},
+	traits::{fungible::Mutate, tokens::Preservation, Contains, Everything},
 };
+use frame_system::{EnsureRoot, EnsureSigned};
+use orml_traits::parameter_type_with_key;
 use sp_core::{ConstU64, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, BlockNumberProvider, Convert, IdentityLookup},
 	BuildStorage,
 };
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
 
 type AccountId = u64;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 pub const TREASURY: AccountId = 42;
+pub const SUPPORTED_ASSET_ID: u32 = 1;
+pub const UNSUPPORTED_ASSET_ID: u32 = 2;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -36,6 +42,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Balances: pallet_balances,
+		Tokens: orml_tokens,
 		Orders: crate::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -79,7 +86,7 @@ impl pallet_balances::Config for Test {
 	type ExistentialDeposit = ConstU64<1>;
 	type AccountStore = System;
 	type WeightInfo = ();
-	type MaxLocks = ();
+	type MaxLocks = ConstU32<10>;
 	type MaxHolds = ();
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
@@ -89,6 +96,33 @@ impl pallet_balances::Config for Test {
 	type MaxFreezes = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		1
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = ();
+}
+
+pub struct SupportedAssets;
+impl Contains<u32> for SupportedAssets {
+	fn contains(asset: &u32) -> bool {
+		*asset == SUPPORTED_ASSET_ID
+	}
+}
+
 pub struct OrderCreationFeeHandler;
 impl FeeHandler<AccountId, u64> for OrderCreationFeeHandler {
 	fn handle(who: &AccountId, fee: u64) -> DispatchResult {
@@ -97,6 +131,19 @@ impl FeeHandler<AccountId, u64> for OrderCreationFeeHandler {
 	}
 }
 
+pub struct CancellationPenaltyHandler;
+impl FeeHandler<AccountId, u64> for CancellationPenaltyHandler {
+	fn handle(who: &AccountId, penalty: u64) -> DispatchResult {
+		<Test as crate::Config>::Currency::transfer(
+			who,
+			&TREASURY,
+			penalty,
+			Preservation::Preserve,
+		)?;
+		Ok(())
+	}
+}
+
 parameter_types! {
 	pub static RelayBlockNumber: u64 = 0;
 }
@@ -116,15 +163,53 @@ impl Convert<OrderId, AccountId> for OrderToAccountId {
 	}
 }
 
+/// Treats signed origins as XCM messages sent by the sibling parachain with the same id.
+pub struct EnsureSiblingOrigin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingOrigin {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		EnsureSigned::<AccountId>::try_origin(o)
+			.map(|who| MultiLocation::new(1, X1(Parachain(who as u32))))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		EnsureSigned::<AccountId>::try_successful_origin()
+	}
+}
+
+/// The sovereign account of a sibling parachain is the account with the same id.
+pub struct SiblingToAccountId;
+impl ConvertLocation<AccountId> for SiblingToAccountId {
+	fn convert_location(location: &MultiLocation) -> Option<AccountId> {
+		match location {
+			MultiLocation { parents: 1, interior: X1(Parachain(id)) } => Some((*id).into()),
+			_ => None,
+		}
+	}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OrderCreationCost = ConstU64<100>;
 	type MinimumContribution = ConstU64<50>;
+	type BulkPeriod = ConstU32<5040>;
 	type RCBlockNumberProvider = RelayBlockNumberProvider;
 	type OrderToAccountId = OrderToAccountId;
 	type TimeslicePeriod = ConstU64<80>;
 	type OrderCreationFeeHandler = OrderCreationFeeHandler;
+	type CancellationPenaltyHandler = CancellationPenaltyHandler;
+	type CancellationPenaltyOrigin = EnsureRoot<AccountId>;
+	type AssetId = u32;
+	type Assets = Tokens;
+	type SupportedAssets = SupportedAssets;
+	type MaxOrderAssets = ConstU32<1>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAssetId = ConstU32<SUPPORTED_ASSET_ID>;
+	type XcmOrigin = EnsureSiblingOrigin;
+	type LocationToAccountId = SiblingToAccountId;
 	type WeightInfo = ();
 }
 
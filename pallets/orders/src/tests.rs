@@ -13,11 +13,14 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{mock::*, Error, Event, Order, ParaId, Requirements};
+use crate::{mock::*, Contributions, Error, Event, Order, ParaId, Requirements, WeightInfo};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{Currency, Get},
+	traits::{Currency, Get, Hooks, LockableCurrency, WithdrawReasons},
+	weights::Weight,
 };
+use order_primitives::{OrderFulfillment, OrderId};
+use orml_traits::MultiCurrency;
 use sp_runtime::{traits::Convert, ArithmeticError, DispatchError, TokenError};
 
 #[test]
@@ -37,22 +40,44 @@ fn create_order_works() {
 			Orders::create_order(
 				RuntimeOrigin::signed(creator.clone()),
 				para_id,
-				requirements.clone()
+				requirements.clone(),
+				None
 			),
 			DispatchError::Token(TokenError::FundsUnavailable)
 		);
 
 		<Test as crate::Config>::Currency::make_free_balance_be(&creator, 1000u32.into());
 
+		// The maximum reward must be non-zero:
+		assert_noop!(
+			Orders::create_order(
+				RuntimeOrigin::signed(creator.clone()),
+				para_id,
+				requirements.clone(),
+				Some(0)
+			),
+			Error::<Test>::InvalidReward
+		);
+
 		assert_ok!(Orders::create_order(
 			RuntimeOrigin::signed(creator.clone()),
 			para_id,
-			requirements.clone()
+			requirements.clone(),
+			None
 		));
 
 		// Check storage items
 		assert_eq!(Orders::next_order_id(), 1);
-		assert_eq!(Orders::orders(0), Some(Order { para_id, creator: 1, requirements }));
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				para_id,
+				creator: 1,
+				requirements,
+				max_reward: None,
+				funding_target: None
+			})
+		);
 		assert!(Orders::orders(1).is_none());
 
 		// Balance should be reduced due to fee payment:
@@ -87,11 +112,12 @@ fn anyone_can_cancel_expired_order() {
 		assert_ok!(Orders::create_order(
 			RuntimeOrigin::signed(creator.clone()),
 			para_id,
-			requirements.clone()
+			requirements.clone(),
+			None
 		));
 
-		// Cannot cancel a non-expired order:
-		assert_noop!(Orders::cancel_order(RuntimeOrigin::signed(1), 0), Error::<Test>::NotAllowed);
+		// Only the creator can cancel a non-expired order:
+		assert_noop!(Orders::cancel_order(RuntimeOrigin::signed(2), 0), Error::<Test>::NotAllowed);
 
 		// Anyone can cancel expired order:
 		RelayBlockNumber::set(9 * timeslice);
@@ -105,6 +131,41 @@ fn anyone_can_cancel_expired_order() {
 	});
 }
 
+#[test]
+fn creator_can_cancel_order_early() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		let creator = 1;
+
+		// Only the configured origin can set the penalty:
+		assert_noop!(
+			Orders::set_cancellation_penalty(RuntimeOrigin::signed(creator), 50),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Orders::set_cancellation_penalty(RuntimeOrigin::root(), 50));
+		assert_eq!(Orders::cancellation_penalty(), 50);
+		System::assert_last_event(Event::CancellationPenaltyUpdated { penalty: 50 }.into());
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(creator),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 500));
+
+		// The creator cancels the order before it expires and pays the penalty:
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(creator), 0));
+		assert!(Orders::orders(0).is_none());
+		assert_eq!(Balances::free_balance(creator), 850);
+		assert_eq!(Balances::free_balance(TREASURY), 150);
+		System::assert_last_event(Event::OrderRemoved { order_id: 0, by: creator }.into());
+
+		// Contributors get their funds back:
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(3), 0));
+		assert_eq!(Balances::free_balance(3), 1000);
+	});
+}
+
 #[test]
 fn contribute_works() {
 	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
@@ -112,7 +173,8 @@ fn contribute_works() {
 		assert_ok!(Orders::create_order(
 			RuntimeOrigin::signed(1),
 			2000.into(),
-			Requirements { begin: 0, end: 8, core_occupancy: 28800 }
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
 		));
 
 		// Invalid order id
@@ -176,7 +238,8 @@ fn remove_contribution_works() {
 		assert_ok!(Orders::create_order(
 			RuntimeOrigin::signed(1),
 			2000.into(),
-			Requirements { begin: 0, end: 8, core_occupancy: 28800 }
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
 		));
 
 		// Order is not cancelled
@@ -216,3 +279,669 @@ fn remove_contribution_works() {
 		);
 	});
 }
+
+#[test]
+fn recurring_order_works() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+
+		// The reward must be non-zero
+		assert_noop!(
+			Orders::create_recurring_order(
+				RuntimeOrigin::signed(1),
+				2000.into(),
+				requirements.clone(),
+				0
+			),
+			Error::<Test>::InvalidReward
+		);
+
+		assert_ok!(Orders::create_recurring_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			requirements.clone(),
+			300
+		));
+		assert_eq!(Orders::recurring_orders(0), Some(()));
+		System::assert_last_event(Event::RecurringOrderCreated { order_id: 0, reward: 300 }.into());
+
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 500));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(2), 0, 200));
+		assert_eq!(Orders::total_contributions(0), 700);
+
+		// Only the reward is paid out of the contributions
+		let order_account = OrderToAccountId::convert(0);
+		assert_eq!(Orders::reward(&0, 700), 300);
+		assert_eq!(Orders::reward(&0, 200), 200);
+
+		// The order gets renewed for the next bulk period while it's funded
+		Balances::make_free_balance_be(&order_account, 400);
+		Orders::on_fulfilled(&0);
+
+		let period = <Test as crate::Config>::BulkPeriod::get();
+		let renewed = Requirements { begin: period, end: period + 8, core_occupancy: 28800 };
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				para_id: 2000.into(),
+				creator: 1,
+				requirements: renewed.clone(),
+				max_reward: Some(300),
+				funding_target: None
+			})
+		);
+		System::assert_last_event(
+			Event::OrderRenewed { order_id: 0, requirements: renewed }.into(),
+		);
+
+		// The order stops once the funds no longer cover the reward, without the creator paying
+		// the cancellation penalty
+		assert_ok!(Orders::set_cancellation_penalty(RuntimeOrigin::root(), 50));
+		Balances::make_free_balance_be(&order_account, 100);
+		Orders::on_fulfilled(&0);
+		assert!(Orders::orders(0).is_none());
+		assert!(Orders::recurring_orders(0).is_none());
+		assert_eq!(Balances::free_balance(1), 900);
+		assert_eq!(Balances::free_balance(TREASURY), 100);
+
+		// The remaining funds are shared pro rata among the contributors
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(3), 0));
+		System::assert_last_event(
+			Event::ContributionRemoved { order_id: 0, who: 3, amount: 71 }.into(),
+		);
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(2), 0));
+		System::assert_last_event(
+			Event::ContributionRemoved { order_id: 0, who: 2, amount: 29 }.into(),
+		);
+		assert_eq!(Balances::free_balance(order_account), 0);
+		assert_eq!(Orders::total_contributions(0), 0);
+	});
+}
+
+#[test]
+fn creator_can_cancel_recurring_order() {
+	new_test_ext(vec![(1, 1000), (2, 1000)]).execute_with(|| {
+		assert_ok!(Orders::create_recurring_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			300
+		));
+
+		// Only the creator can cancel the order before it expires
+		assert_noop!(Orders::cancel_order(RuntimeOrigin::signed(2), 0), Error::<Test>::NotAllowed);
+
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(1), 0));
+		assert!(Orders::orders(0).is_none());
+		assert!(Orders::recurring_orders(0).is_none());
+		System::assert_last_event(Event::OrderRemoved { order_id: 0, by: 1 }.into());
+	});
+}
+
+#[test]
+fn update_order_works() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+
+		// Only the creator can update the order:
+		let new_requirements = Requirements { begin: 2, end: 10, core_occupancy: 28800 };
+		assert_noop!(
+			Orders::update_order(RuntimeOrigin::signed(2), 0, new_requirements.clone()),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Orders::update_order(RuntimeOrigin::signed(1), 1, new_requirements.clone()),
+			Error::<Test>::InvalidOrderId
+		);
+
+		// Without contributions the requirements can change freely:
+		assert_ok!(Orders::update_order(RuntimeOrigin::signed(1), 0, new_requirements.clone()));
+		assert_eq!(
+			Orders::orders(0).map(|order| order.requirements),
+			Some(new_requirements.clone())
+		);
+		System::assert_last_event(
+			Event::OrderUpdated { order_id: 0, requirements: new_requirements.clone() }.into(),
+		);
+
+		// The period must be valid:
+		assert_noop!(
+			Orders::update_order(
+				RuntimeOrigin::signed(1),
+				0,
+				Requirements { begin: 10, end: 10, core_occupancy: 28800 }
+			),
+			Error::<Test>::InvalidPeriod
+		);
+		let timeslice: u64 = <Test as crate::Config>::TimeslicePeriod::get();
+		RelayBlockNumber::set(10 * timeslice);
+		assert_noop!(
+			Orders::update_order(
+				RuntimeOrigin::signed(1),
+				0,
+				Requirements { begin: 2, end: 8, core_occupancy: 28800 }
+			),
+			Error::<Test>::InvalidPeriod
+		);
+		RelayBlockNumber::set(0);
+
+		// Once contributed to, the period is fixed:
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 100));
+		assert_noop!(
+			Orders::update_order(RuntimeOrigin::signed(1), 0, requirements),
+			Error::<Test>::OrderHasContributions
+		);
+
+		// ... and the occupancy can't be lowered:
+		assert_noop!(
+			Orders::update_order(
+				RuntimeOrigin::signed(1),
+				0,
+				Requirements { begin: 2, end: 10, core_occupancy: 14400 }
+			),
+			Error::<Test>::OrderHasContributions
+		);
+
+		let new_requirements = Requirements { begin: 2, end: 10, core_occupancy: 57600 };
+		assert_ok!(Orders::update_order(RuntimeOrigin::signed(1), 0, new_requirements.clone()));
+		assert_eq!(Orders::orders(0).map(|order| order.requirements), Some(new_requirements));
+	});
+}
+
+#[test]
+fn funding_target_works() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+
+		// Only the creator can set the target:
+		assert_noop!(
+			Orders::set_funding_target(RuntimeOrigin::signed(2), 0, Some(500)),
+			Error::<Test>::NotAllowed
+		);
+		assert_ok!(Orders::set_funding_target(RuntimeOrigin::signed(1), 0, Some(500)));
+		assert_eq!(Orders::orders(0).and_then(|order| order.funding_target), Some(500));
+		System::assert_last_event(
+			Event::FundingTargetSet { order_id: 0, funding_target: Some(500) }.into(),
+		);
+
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(2), 0, 300));
+		System::assert_last_event(Event::Contributed { order_id: 0, who: 2, amount: 300 }.into());
+
+		// Reaching the target emits an event:
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 300));
+		System::assert_last_event(Event::OrderFullyFunded { order_id: 0, total: 600 }.into());
+
+		// Only once:
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 100));
+		System::assert_last_event(Event::Contributed { order_id: 0, who: 3, amount: 100 }.into());
+
+		// Setting an already reached target emits the event right away:
+		assert_ok!(Orders::set_funding_target(RuntimeOrigin::signed(1), 0, Some(700)));
+		System::assert_last_event(Event::OrderFullyFunded { order_id: 0, total: 700 }.into());
+	});
+}
+
+#[test]
+fn asset_contributions_work() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		assert_ok!(Tokens::deposit(SUPPORTED_ASSET_ID, &2, 1000));
+		assert_ok!(Tokens::deposit(SUPPORTED_ASSET_ID, &3, 1000));
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+
+		assert_noop!(
+			Orders::contribute_asset(RuntimeOrigin::signed(2), 0, UNSUPPORTED_ASSET_ID, 300),
+			Error::<Test>::UnsupportedAsset
+		);
+		assert_noop!(
+			Orders::contribute_asset(RuntimeOrigin::signed(2), 1, SUPPORTED_ASSET_ID, 300),
+			Error::<Test>::InvalidOrderId
+		);
+		assert_noop!(
+			Orders::contribute_asset(RuntimeOrigin::signed(2), 0, SUPPORTED_ASSET_ID, 0),
+			Error::<Test>::InvalidAmount
+		);
+
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(2), 0, SUPPORTED_ASSET_ID, 300));
+		System::assert_last_event(
+			Event::AssetContributed { order_id: 0, who: 2, asset: SUPPORTED_ASSET_ID, amount: 300 }
+				.into(),
+		);
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(3), 0, SUPPORTED_ASSET_ID, 200));
+
+		// Check storage items
+		assert_eq!(Orders::asset_contributions((0, 2, SUPPORTED_ASSET_ID)), 300);
+		assert_eq!(Orders::total_asset_contributions(0).to_vec(), vec![(SUPPORTED_ASSET_ID, 500)]);
+		let order_account = OrderToAccountId::convert(0);
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &order_account), 500);
+
+		// Contributions can't be removed while the order is active
+		assert_noop!(
+			Orders::remove_asset_contribution(RuntimeOrigin::signed(2), 0, SUPPORTED_ASSET_ID),
+			Error::<Test>::OrderNotCancelled
+		);
+
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(1), 0));
+
+		assert_ok!(Orders::remove_asset_contribution(
+			RuntimeOrigin::signed(2),
+			0,
+			SUPPORTED_ASSET_ID
+		));
+		System::assert_last_event(
+			Event::AssetContributionRemoved {
+				order_id: 0,
+				who: 2,
+				asset: SUPPORTED_ASSET_ID,
+				amount: 300,
+			}
+			.into(),
+		);
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &2), 1000);
+		assert_eq!(Orders::total_asset_contributions(0).to_vec(), vec![(SUPPORTED_ASSET_ID, 200)]);
+
+		assert_noop!(
+			Orders::remove_asset_contribution(RuntimeOrigin::signed(2), 0, SUPPORTED_ASSET_ID),
+			Error::<Test>::NoContribution
+		);
+
+		assert_ok!(Orders::remove_asset_contribution(
+			RuntimeOrigin::signed(3),
+			0,
+			SUPPORTED_ASSET_ID
+		));
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &3), 1000);
+		assert!(Orders::total_asset_contributions(0).is_empty());
+
+		// Recurring orders can only be funded with `Currency`
+		assert_ok!(Orders::create_recurring_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			300
+		));
+		assert_noop!(
+			Orders::contribute_asset(RuntimeOrigin::signed(2), 1, SUPPORTED_ASSET_ID, 300),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn cancelled_orders_are_refunded_on_idle() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		assert_ok!(Tokens::deposit(SUPPORTED_ASSET_ID, &3, 1000));
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(2), 0, 500));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(3), 0, 200));
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(3), 0, SUPPORTED_ASSET_ID, 300));
+
+		// Some dust is sent directly to the order account.
+		let order_account = OrderToAccountId::convert(0);
+		Balances::make_free_balance_be(&order_account, 710);
+
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Orders::cancelled_orders(0), Some(1));
+
+		// Nothing happens without spare weight.
+		assert_eq!(Orders::on_idle(1, Weight::zero()), Weight::zero());
+		assert_eq!(Contributions::<Test>::iter_prefix(0).count(), 2);
+
+		// With weight for a single refund only one contributor gets refunded.
+		let refund_weight = <() as WeightInfo>::remove_contribution()
+			.max(<() as WeightInfo>::remove_asset_contribution());
+		Orders::on_idle(1, refund_weight);
+		assert_eq!(Contributions::<Test>::iter_prefix(0).count(), 1);
+		assert_eq!(Orders::cancelled_orders(0), Some(1));
+
+		Orders::on_idle(1, Weight::MAX);
+		System::assert_last_event(Event::OrderRefunded { order_id: 0 }.into());
+
+		// All contributors got refunded and the dust went to the creator.
+		assert_eq!(Balances::free_balance(2), 1000);
+		assert_eq!(Balances::free_balance(3), 1000);
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &3), 1000);
+		assert_eq!(Balances::free_balance(1), 910);
+
+		// The order storage got cleared and the order account got reaped.
+		assert_eq!(Contributions::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(Orders::asset_contributions((0, 3, SUPPORTED_ASSET_ID)), 0);
+		assert_eq!(Orders::total_contributions(0), 0);
+		assert!(Orders::total_asset_contributions(0).is_empty());
+		assert_eq!(Orders::cancelled_orders(0), None);
+		assert_eq!(Balances::free_balance(order_account), 0);
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &order_account), 0);
+	});
+}
+
+#[test]
+fn failed_refunds_can_be_claimed() {
+	new_test_ext(vec![(1, 1000), (2, 1000), (3, 1000)]).execute_with(|| {
+		assert_ok!(Tokens::deposit(SUPPORTED_ASSET_ID, &3, 1000));
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(2), 0, 500));
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(3), 0, SUPPORTED_ASSET_ID, 300));
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(1), 0));
+
+		// The order funds can't be moved, so refunding the contribution fails.
+		let order_account = OrderToAccountId::convert(0);
+		Balances::set_lock(*b"testlock", &order_account, 500, WithdrawReasons::all());
+
+		Orders::on_idle(1, Weight::MAX);
+		System::assert_has_event(Event::RefundFailed { order_id: 0, who: 2 }.into());
+
+		// The contribution is kept, while the other contributor got refunded.
+		assert_eq!(Orders::contributions(0, 2), 500);
+		assert_eq!(Orders::total_contributions(0), 500);
+		assert_eq!(Tokens::free_balance(SUPPORTED_ASSET_ID, &3), 1000);
+
+		// The refunds are done, without the funds of the contributor going to the creator.
+		assert_eq!(Orders::cancelled_orders(0), None);
+		assert_eq!(Orders::failed_refunds(0, 2), None);
+		assert_eq!(Balances::free_balance(order_account), 500);
+		assert_eq!(Balances::free_balance(1), 900);
+
+		// The contributor claims back their contribution once the funds can be moved.
+		Balances::remove_lock(*b"testlock", &order_account);
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Balances::free_balance(2), 1000);
+		assert_eq!(Orders::total_contributions(0), 0);
+	});
+}
+
+#[test]
+fn create_order_via_xcm_works() {
+	new_test_ext(vec![(2000, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+
+		// Only sibling parachains can create orders through XCM.
+		assert_noop!(
+			Orders::create_order_via_xcm(RuntimeOrigin::root(), requirements.clone(), 100),
+			DispatchError::BadOrigin
+		);
+
+		// The contribution must be large enough.
+		assert_noop!(
+			Orders::create_order_via_xcm(RuntimeOrigin::signed(2000), requirements.clone(), 10),
+			Error::<Test>::InvalidAmount
+		);
+
+		// The sibling parachain with id 2000 sends the message. In the mock its sovereign account
+		// is 2000.
+		assert_ok!(Orders::create_order_via_xcm(
+			RuntimeOrigin::signed(2000),
+			requirements.clone(),
+			300
+		));
+		System::assert_has_event(Event::OrderCreated { order_id: 0, by: 2000 }.into());
+		System::assert_last_event(
+			Event::Contributed { order_id: 0, who: 2000, amount: 300 }.into(),
+		);
+
+		// The order is created for the sibling parachain and funded by its sovereign account.
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				creator: 2000,
+				para_id: 2000.into(),
+				requirements,
+				max_reward: None,
+				funding_target: None,
+			})
+		);
+		assert_eq!(Orders::contributions(0, 2000), 300);
+		assert_eq!(Balances::free_balance(2000), 600);
+		assert_eq!(Balances::free_balance(OrderToAccountId::convert(0)), 300);
+	});
+}
+
+#[test]
+fn order_indexes_work() {
+	new_test_ext(vec![(1, 1000), (2, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2),
+			2001.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(1),
+			2001.into(),
+			requirements.clone(),
+			None
+		));
+
+		let order_ids = |orders: Vec<(OrderId, _)>| {
+			let mut ids: Vec<OrderId> = orders.into_iter().map(|(id, _)| id).collect();
+			ids.sort();
+			ids
+		};
+
+		assert_eq!(order_ids(Orders::orders_of_para(2000.into())), vec![0]);
+		assert_eq!(order_ids(Orders::orders_of_para(2001.into())), vec![1, 2]);
+		assert_eq!(order_ids(Orders::orders_of_creator(&1)), vec![0, 2]);
+		assert_eq!(order_ids(Orders::orders_of_creator(&2)), vec![1]);
+
+		// Open orders can be queried page by page.
+		let first_page = Orders::open_orders(None, 2);
+		assert_eq!(first_page.len(), 2);
+		let second_page = Orders::open_orders(first_page.last().map(|(id, _)| *id), 2);
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(order_ids([first_page, second_page].concat()), vec![0, 1, 2]);
+
+		// Removed orders are removed from the indexes.
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(1), 0));
+		assert!(Orders::orders_of_para(2000.into()).is_empty());
+		assert_eq!(order_ids(Orders::orders_of_creator(&1)), vec![2]);
+		assert_eq!(order_ids(Orders::open_orders(None, 10)), vec![1, 2]);
+
+		// Expired orders are not open anymore.
+		RelayBlockNumber::set(8 * 80);
+		assert!(Orders::open_orders(None, 10).is_empty());
+		assert!(Orders::orders_of_para(2001.into()).is_empty());
+		assert!(Orders::orders_of_creator(&1).is_empty());
+	});
+}
+
+#[test]
+fn transfer_contribution_works() {
+	new_test_ext(vec![(1, 1000), (2, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(1), 0, 500));
+
+		// Invalid order id:
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 1, 4, 100),
+			Error::<Test>::InvalidOrderId
+		);
+		// Invalid amount:
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 0),
+			Error::<Test>::InvalidAmount
+		);
+		// Cannot transfer to oneself:
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 1, 100),
+			Error::<Test>::SelfTransfer
+		);
+		// Cannot transfer more than contributed:
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 501),
+			Error::<Test>::InsufficientContribution
+		);
+		// Neither side can end up below the minimum contribution:
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 49),
+			Error::<Test>::InvalidAmount
+		);
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 451),
+			Error::<Test>::InvalidAmount
+		);
+
+		// Part of the contribution can be transferred:
+		assert_ok!(Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 200));
+		System::assert_last_event(
+			Event::ContributionTransferred { order_id: 0, from: 1, to: 4, amount: 200 }.into(),
+		);
+		assert_eq!(Orders::contributions(0, 1), 300);
+		assert_eq!(Orders::contributions(0, 4), 200);
+		assert_eq!(Orders::total_contributions(0), 500);
+
+		// As well as the rest of it:
+		assert_ok!(Orders::transfer_contribution(RuntimeOrigin::signed(1), 0, 4, 300));
+		assert!(!Contributions::<Test>::contains_key(0, 1));
+		assert_eq!(Orders::contributions(0, 4), 500);
+		assert_eq!(Orders::total_contributions(0), 500);
+
+		// Once the order is cancelled the positions are final and the refund goes to the new
+		// owner:
+		assert_ok!(Orders::cancel_order(RuntimeOrigin::signed(2), 0));
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(4), 0, 1, 500),
+			Error::<Test>::InvalidOrderId
+		);
+		assert_noop!(
+			Orders::remove_contribution(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NoContribution
+		);
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(4), 0));
+		assert_eq!(Balances::free_balance(4), 500);
+
+		// Contributions of accounts that also contributed in other assets cannot be transferred:
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(1), 1, 200));
+		assert_ok!(Tokens::deposit(SUPPORTED_ASSET_ID, &1, 100));
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(1), 1, SUPPORTED_ASSET_ID, 100));
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 1, 4, 200),
+			Error::<Test>::HasAssetContributions
+		);
+
+		// Contributions to fulfilled orders cannot be transferred:
+		assert_ok!(Orders::create_order(RuntimeOrigin::signed(2), 2000.into(), requirements, None));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(1), 2, 200));
+		Orders::on_fulfilled(&2);
+		assert_noop!(
+			Orders::transfer_contribution(RuntimeOrigin::signed(1), 2, 4, 200),
+			Error::<Test>::InvalidOrderId
+		);
+	});
+}
+
+#[test]
+fn migrations_work() {
+	use crate::migrations::*;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	type Migrations = (
+		v1::MigrateToV1<Test>,
+		v2::MigrateToV2<Test>,
+		v3::MigrateToV3<Test>,
+		v4::MigrateToV4<Test>,
+	);
+
+	new_test_ext(vec![(2, 1000), (3, 1000)]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+
+		StorageVersion::new(0).put::<Orders>();
+		v0::Orders::<Test>::insert(
+			0,
+			v0::Order { creator: 1, para_id: 2000.into(), requirements: requirements.clone() },
+		);
+		crate::NextOrderId::<Test>::put(1);
+		Contributions::<Test>::insert(0, 2, 100);
+		Contributions::<Test>::insert(0, 3, 50);
+
+		Migrations::on_runtime_upgrade();
+
+		assert_eq!(Orders::on_chain_storage_version(), StorageVersion::new(4));
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				creator: 1,
+				para_id: 2000.into(),
+				requirements,
+				max_reward: None,
+				funding_target: None,
+			})
+		);
+		assert_eq!(Orders::total_contributions(0), 150);
+
+		// The migrated order is indexed:
+		assert_eq!(Orders::orders_of_para(2000.into()).len(), 1);
+		assert_eq!(Orders::orders_of_creator(&1).len(), 1);
+	});
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	use crate::migrations::{v0, v1, v2};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext(vec![]).execute_with(|| {
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+		let order =
+			|| v0::Order { creator: 1, para_id: 2000.into(), requirements: requirements.clone() };
+
+		StorageVersion::new(1).put::<Orders>();
+		v0::Orders::<Test>::insert(0, order());
+		v0::Orders::<Test>::insert(1, order());
+		// The second order is recurring:
+		v1::RecurringOrders::<Test>::insert(1, 500);
+
+		v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Orders::on_chain_storage_version(), StorageVersion::new(2));
+		assert_eq!(v2::Orders::<Test>::get(0).unwrap().max_reward, None);
+		// The reward of the recurring order moved to the order:
+		assert_eq!(v2::Orders::<Test>::get(1).unwrap().max_reward, Some(500));
+		assert_eq!(crate::RecurringOrders::<Test>::get(1), Some(()));
+	});
+}
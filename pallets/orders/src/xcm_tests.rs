@@ -0,0 +1,163 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	xcm_mock::{
+		child_account, parachain, relay_chain, sibling_account, MockNet, OrdersPara, Relay,
+		SiblingPara, INITIAL_BALANCE, ORDERS_PARA_ID, SIBLING_PARA_ID,
+	},
+	Event, Order, Requirements,
+};
+use codec::Encode;
+use frame_support::assert_ok;
+use parachain::{Balances, OrderToAccountId, Orders, PolkadotXcm, RuntimeCall, System, TREASURY};
+use sp_runtime::traits::Convert;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+const TRANSFERRED: u128 = 100_000;
+const FEES: u128 = 10_000;
+
+/// Moves relay chain assets of the sibling parachain into its sovereign account on the parachain
+/// running the orders pallet, with the relay chain acting as the reserve.
+fn fund_sibling_sovereign_account(amount: u128) {
+	SiblingPara::execute_with(|| {
+		assert_ok!(PolkadotXcm::send_xcm(
+			Here,
+			Parent,
+			Xcm(vec![
+				WithdrawAsset((Here, amount).into()),
+				DepositReserveAsset {
+					assets: AllCounted(1).into(),
+					dest: Parachain(ORDERS_PARA_ID).into(),
+					xcm: Xcm(vec![
+						BuyExecution { fees: (Parent, FEES).into(), weight_limit: Unlimited },
+						DepositAsset {
+							assets: AllCounted(1).into(),
+							beneficiary: (Parent, Parachain(SIBLING_PARA_ID)).into(),
+						},
+					]),
+				},
+			]),
+		));
+	});
+}
+
+/// A message paying for its own execution with assets of the sovereign account before creating
+/// and funding an order.
+fn create_order_message(origin_kind: OriginKind, requirements: Requirements) -> Xcm<()> {
+	let call =
+		RuntimeCall::Orders(crate::Call::create_order_via_xcm { requirements, contribution: 500 });
+	Xcm(vec![
+		WithdrawAsset((Parent, FEES).into()),
+		BuyExecution { fees: (Parent, FEES).into(), weight_limit: Unlimited },
+		DepositAsset {
+			assets: AllCounted(1).into(),
+			beneficiary: (Parent, Parachain(SIBLING_PARA_ID)).into(),
+		},
+		Transact {
+			origin_kind,
+			require_weight_at_most: Weight::from_parts(5_000_000_000, 1024 * 1024),
+			call: call.encode().into(),
+		},
+	])
+}
+
+#[test]
+fn sibling_can_create_and_fund_order_via_xcm() {
+	MockNet::reset();
+
+	let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+	let sovereign_account = sibling_account(SIBLING_PARA_ID);
+
+	// The sovereign account of the sibling starts without any funds:
+	OrdersPara::execute_with(|| {
+		assert_eq!(Balances::free_balance(&sovereign_account), 0);
+	});
+
+	fund_sibling_sovereign_account(TRANSFERRED);
+	Relay::execute_with(|| {
+		assert_eq!(
+			relay_chain::Balances::free_balance(child_account(SIBLING_PARA_ID)),
+			INITIAL_BALANCE - TRANSFERRED
+		);
+		assert_eq!(relay_chain::Balances::free_balance(child_account(ORDERS_PARA_ID)), TRANSFERRED);
+	});
+	OrdersPara::execute_with(|| {
+		let funded = Balances::free_balance(&sovereign_account);
+		// Only the execution fees were taken from the transferred assets.
+		assert!(funded > TRANSFERRED - FEES && funded < TRANSFERRED);
+	});
+
+	// The call must be dispatched with the XCM origin of the sibling parachain, a signed origin
+	// is not enough.
+	SiblingPara::execute_with(|| {
+		assert_ok!(PolkadotXcm::send_xcm(
+			Here,
+			(Parent, Parachain(ORDERS_PARA_ID)),
+			create_order_message(OriginKind::SovereignAccount, requirements.clone()),
+		));
+	});
+	let funded = OrdersPara::execute_with(|| {
+		assert_eq!(Orders::orders(0), None);
+		assert_eq!(Orders::contributions(0, sovereign_account.clone()), 0);
+		Balances::free_balance(&sovereign_account)
+	});
+
+	SiblingPara::execute_with(|| {
+		assert_ok!(PolkadotXcm::send_xcm(
+			Here,
+			(Parent, Parachain(ORDERS_PARA_ID)),
+			create_order_message(OriginKind::Xcm, requirements.clone()),
+		));
+	});
+
+	OrdersPara::execute_with(|| {
+		System::assert_has_event(
+			Event::<parachain::Runtime>::OrderCreated {
+				order_id: 0,
+				by: sovereign_account.clone(),
+			}
+			.into(),
+		);
+		System::assert_has_event(
+			Event::<parachain::Runtime>::Contributed {
+				order_id: 0,
+				who: sovereign_account.clone(),
+				amount: 500,
+			}
+			.into(),
+		);
+
+		// The order is created for the sibling parachain and funded by its sovereign account.
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				creator: sovereign_account.clone(),
+				para_id: SIBLING_PARA_ID.into(),
+				requirements,
+				max_reward: None,
+				funding_target: None,
+			})
+		);
+		assert_eq!(Orders::contributions(0, sovereign_account.clone()), 500);
+		assert_eq!(Orders::total_contributions(0), 500);
+		assert_eq!(Balances::free_balance(OrderToAccountId::convert(0)), 500);
+		assert_eq!(Balances::free_balance(&TREASURY), 100);
+		// The creation fee, the contribution and the execution fees were paid from the
+		// sovereign account.
+		assert!(Balances::free_balance(&sovereign_account) < funded - 100 - 500);
+	});
+}
@@ -20,7 +20,7 @@
 use super::*;
 
 use frame_benchmarking::v2::*;
-use frame_support::assert_ok;
+use frame_support::{assert_ok, traits::Get};
 use frame_system::RawOrigin;
 use pallet_broker::{CoreMask, RegionId, RegionRecord};
 use sp_runtime::SaturatedConversion;
@@ -65,6 +65,140 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn purchase_and_fulfill_order() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
+		let seller: T::AccountId = account("seller", 0, SEED);
+
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 57600, // Full core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&alice.clone(),
+			u64::MAX.saturated_into(),
+		);
+		assert_ok!(T::Orders::create_order(alice.clone(), 2000.into(), requirements.clone()));
+
+		// Fund the order so that the reward covers the listing price:
+		let order_account = T::OrderToAccountId::convert(0);
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&order_account,
+			u64::MAX.saturated_into(),
+		);
+
+		// List a region which meets the requirements:
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		let record: RegionRecordOf<T> = RegionRecord { end: 8, owner: seller.clone(), paid: None };
+		T::Regions::create_region(region_id, record, seller.clone())?;
+		T::Market::list(&seller, region_id, 1_000u32.into())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, region_id);
+
+		assert_last_event::<T>(Event::RegionAssigned { region_id, para_id: 2000.into() }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn contribute_region(
+		n: Linear<1, { T::MaxOrderRegions::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 57600, // Full core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&alice.clone(),
+			u64::MAX.saturated_into(),
+		);
+		assert_ok!(T::Orders::create_order(alice.clone(), 2000.into(), requirements.clone()));
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&T::OrderToAccountId::convert(0),
+			u64::MAX.saturated_into(),
+		);
+
+		// The core is split among `n` regions, the last one completes the order.
+		let chunk = |i: u32| CoreMask::from_chunk(i * 80 / n, (i + 1) * 80 / n);
+		for i in 0..n {
+			let owner: T::AccountId = account("contributor", i, SEED);
+			let region_id = RegionId { begin: 0, core: 0, mask: chunk(i) };
+			let record: RegionRecordOf<T> =
+				RegionRecord { end: 8, owner: owner.clone(), paid: None };
+			T::Regions::create_region(region_id, record, owner.clone())?;
+
+			if i < n - 1 {
+				crate::Pallet::<T>::contribute_region(
+					RawOrigin::Signed(owner).into(),
+					0,
+					region_id,
+				)?;
+			}
+		}
+
+		let region_id = RegionId { begin: 0, core: 0, mask: chunk(n - 1) };
+		let contributor: T::AccountId = account("contributor", n - 1, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(contributor), 0, region_id);
+
+		assert_last_event::<T>(Event::RegionAssigned { region_id, para_id: 2000.into() }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_region() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let alice: T::AccountId = account("alice", 0, SEED);
+
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 57600, // Full core.
+		};
+
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&alice.clone(),
+			u64::MAX.saturated_into(),
+		);
+		assert_ok!(T::Orders::create_order(alice.clone(), 2000.into(), requirements.clone()));
+
+		// The withdrawn region is the last of the contributions.
+		let max = T::MaxOrderRegions::get();
+		for i in 0..max {
+			let region_id = RegionId { begin: 0, core: i as u16, mask: CoreMask::from_chunk(0, 1) };
+			let record: RegionRecordOf<T> =
+				RegionRecord { end: 8, owner: caller.clone(), paid: None };
+			T::Regions::create_region(region_id, record, caller.clone())?;
+			crate::Pallet::<T>::contribute_region(
+				RawOrigin::Signed(caller.clone()).into(),
+				0,
+				region_id,
+			)?;
+		}
+
+		let region_id =
+			RegionId { begin: 0, core: (max - 1) as u16, mask: CoreMask::from_chunk(0, 1) };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, region_id);
+
+		assert_last_event::<T>(
+			Event::RegionWithdrawn { order_id: 0, region_id, contributor: caller }.into(),
+		);
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn assign() -> Result<(), BenchmarkError> {
 		let caller: T::AccountId = whitelisted_caller();
@@ -21,11 +21,15 @@ use frame_support::{
 	weights::WeightToFee,
 };
 use nonfungible_primitives::LockableNonFungible;
-use order_primitives::{OrderFactory, OrderId, OrderInspect, ParaId, Requirements};
+use order_primitives::{
+	core_occupancy, Order, OrderFactory, OrderFulfillment, OrderId, OrderInspect, ParaId,
+	Requirements,
+};
 pub use pallet::*;
 use pallet_broker::{RegionId, RegionRecord};
-use region_primitives::{RegionFactory, RegionInspect};
-use sp_runtime::traits::Convert;
+use region_primitives::{RegionFactory, RegionInspect, RegionMarket};
+use scale_info::prelude::vec::Vec;
+use sp_runtime::{traits::Convert, Perbill, Saturating};
 use xcm::opaque::lts::MultiLocation;
 
 #[cfg(test)]
@@ -46,6 +50,8 @@ const LOG_TARGET: &str = "runtime::order-creator";
 pub type BalanceOf<T> =
 	<<T as crate::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type OrderOf<T> = Order<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
 pub type RegionRecordOf<T> =
 	RegionRecord<<T as frame_system::Config>::AccountId, <T as crate::Config>::Balance>;
 
@@ -78,7 +84,9 @@ pub mod pallet {
 			+ Into<u128>;
 
 		/// Type over which we can access order data.
-		type Orders: OrderInspect<Self::AccountId> + OrderFactory<Self::AccountId>;
+		type Orders: OrderInspect<Self::AccountId, BalanceOf<Self>>
+			+ OrderFactory<Self::AccountId>
+			+ OrderFulfillment<Self::AccountId, BalanceOf<Self>>;
 
 		/// A way for getting the associated account of an order.
 		type OrderToAccountId: Convert<OrderId, Self::AccountId>;
@@ -92,6 +100,13 @@ pub mod pallet {
 			+ RegionInspect<Self::AccountId, Self::Balance, ItemId = u128>
 			+ RegionFactory<Self::AccountId, RegionRecordOf<Self>>;
 
+		/// Type through which listed regions can be bought for orders.
+		type Market: RegionMarket<Self::AccountId, BalanceOf<Self>>;
+
+		/// The maximum number of regions an order can be fulfilled with.
+		#[pallet::constant]
+		type MaxOrderRegions: Get<u32>;
+
 		/// Type assigning the region to the specified task.
 		type RegionAssigner: RegionAssigner;
 
@@ -117,6 +132,19 @@ pub mod pallet {
 	pub type RegionAssignments<T: Config> =
 		StorageMap<_, Blake2_128Concat, RegionId, ParaId, OptionQuery>;
 
+	/// Regions contributed towards fulfilling an order, along with the contributing accounts.
+	///
+	/// The order gets fulfilled once the regions together reach the required core occupancy.
+	#[pallet::storage]
+	#[pallet::getter(fn region_contributions)]
+	pub type RegionContributions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		OrderId,
+		BoundedVec<(RegionId, T::AccountId), T::MaxOrderRegions>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -131,6 +159,10 @@ pub mod pallet {
 		RegionAssigned { region_id: RegionId, para_id: ParaId },
 		/// Region assignment failed.
 		AssignmentFailed(DispatchError),
+		/// A region got contributed towards fulfilling an order.
+		RegionContributed { order_id: OrderId, region_id: RegionId, contributor: T::AccountId },
+		/// A region contributed to an order got withdrawn.
+		RegionWithdrawn { order_id: OrderId, region_id: RegionId, contributor: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -154,6 +186,12 @@ pub mod pallet {
 		NotOwner,
 		/// We didn't find the task to which the region is supposed to be assigned.
 		RegionAssignmentNotFound,
+		/// The region overlaps with a region already contributed to the order.
+		RegionsOverlap,
+		/// The order can't be fulfilled with any more regions.
+		TooManyRegions,
+		/// The region wasn't contributed to the order by the caller.
+		UnknownContribution,
 	}
 
 	#[pallet::call]
@@ -167,13 +205,18 @@ pub mod pallet {
 		/// call the `assign` extrinsic to assign it to specific para. The region will be locked,
 		/// and only assignment is allowed.
 		///
+		/// Regions contributed to the order through `contribute_region` are returned to their
+		/// contributors, unless they also cover the renewed order in case it is a recurring one.
+		///
 		/// ## Arguments:
 		/// - `origin`: Signed origin; the region owner.
 		/// - `order_id`: The order which the caller intends to fulfill.
 		/// - `region_id`: The region that the caller intends to sell to the coretime order. The
 		///   region must match the order requierements otherwise the extrinsic will fail
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::fulfill_order())]
+		#[pallet::weight(T::WeightInfo::fulfill_order().saturating_add(
+			T::WeightInfo::withdraw_region().saturating_mul(T::MaxOrderRegions::get().into())
+		))]
 		pub fn fulfill_order(
 			origin: OriginFor<T>,
 			order_id: OrderId,
@@ -189,51 +232,147 @@ pub mod pallet {
 			let record = region.record.get().ok_or(Error::<T>::RecordUnavailable)?;
 			let order = T::Orders::order(&order_id).ok_or(Error::<T>::UnknownOrder)?;
 
-			Self::ensure_matching_requirements(region_id, record, order.requirements)?;
+			Self::ensure_matching_requirements(region_id, record, order.requirements.clone())?;
 
-			// Transfer the region to the order creator
-			//
-			// We will try to assign the region to the task, however before we do that we will
-			// transfer it to the order creator. This way in case the assignment fails the region
-			// will still be owned by the creator.
-			T::Regions::transfer(&region_id.into(), &order.creator)?;
-			T::Regions::lock(&region_id.into(), None)?;
+			let order_account = T::OrderToAccountId::convert(order_id);
+			let reward = T::Orders::reward(&order_id, T::Currency::free_balance(&order_account));
+
+			Self::do_fulfill_order(who, order_id, order, region_id, reward)
+		}
+
+		/// Extrinsic for buying a listed region and fulfilling an order with it in one step.
+		///
+		/// The listing price is paid from the order reward, and the rest of the reward goes to
+		/// the caller. The call fails if the reward doesn't cover the price of the region.
+		///
+		/// ## Arguments:
+		/// - `origin`: Signed origin; can be anyone.
+		/// - `order_id`: The order which the caller intends to fulfill.
+		/// - `region_id`: The listed region that will be bought for the order. The region must
+		///   match the order requirements otherwise the extrinsic will fail.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::purchase_and_fulfill_order().saturating_add(
+			T::WeightInfo::withdraw_region().saturating_mul(T::MaxOrderRegions::get().into())
+		))]
+		pub fn purchase_and_fulfill_order(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			region_id: RegionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let region = T::Regions::region(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
+			let record = region.record.get().ok_or(Error::<T>::RecordUnavailable)?;
+			let order = T::Orders::order(&order_id).ok_or(Error::<T>::UnknownOrder)?;
+
+			Self::ensure_matching_requirements(region_id, record, order.requirements.clone())?;
 
+			// The region is bought by the order account so that the price is paid from the
+			// reward. The purchase fails if the reward doesn't cover the price.
 			let order_account = T::OrderToAccountId::convert(order_id);
-			let amount = T::Currency::free_balance(&order_account);
+			let reward = T::Orders::reward(&order_id, T::Currency::free_balance(&order_account));
+			let price = T::Market::purchase(&order_account, region_id, reward)?;
 
-			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
-				&order_account,
-				&who,
-				amount,
-				ExistenceRequirement::AllowDeath,
-			)?;
+			Self::do_fulfill_order(who, order_id, order, region_id, reward.saturating_sub(price))
+		}
 
-			// remove the order
-			T::Orders::remove_order(&order_id);
+		/// Extrinsic for contributing a region towards fulfilling an order.
+		///
+		/// Allows fulfilling an order with several regions which together reach the required
+		/// core occupancy. Each region has to cover the timeslices required by the order, and
+		/// the regions must not overlap with each other.
+		///
+		/// The region gets locked until the order is fulfilled or the region is withdrawn. Once
+		/// the contributed regions reach the required occupancy the order gets fulfilled and the
+		/// reward is shared among the contributors pro rata to the occupancy of their regions.
+		///
+		/// ## Arguments:
+		/// - `origin`: Signed origin; the region owner.
+		/// - `order_id`: The order to which the caller intends to contribute.
+		/// - `region_id`: The region that the caller intends to contribute.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::contribute_region(T::MaxOrderRegions::get()))]
+		pub fn contribute_region(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			region_id: RegionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::OrderProcessed {
-				order_id,
-				region_id,
-				seller: who,
-				reward: amount,
-			});
+			let region = T::Regions::region(&region_id.into()).ok_or(Error::<T>::UnknownRegion)?;
+			ensure!(!region.locked, Error::<T>::RegionLocked);
+			ensure!(region.owner == who, Error::<T>::NotOwner);
 
-			// NOTE: If the assignment fails, we don't return an error; instead, we return ok and
-			// allow anyone to attempt to assign the region.
-			if let Err(err) = T::RegionAssigner::assign(region_id, order.para_id) {
-				// Even though the region will be owned by the creator, anyone can assign it to the
-				// task by calling the `assign` extrinsic.
-				RegionAssignments::<T>::insert(region_id, order.para_id);
+			let record = region.record.get().ok_or(Error::<T>::RecordUnavailable)?;
+			let order = T::Orders::order(&order_id).ok_or(Error::<T>::UnknownOrder)?;
+			Self::ensure_matching_period(region_id, &record, &order.requirements)?;
 
-				Self::deposit_event(Event::AssignmentFailed(err));
+			// The order might have changed since the other regions got contributed.
+			let mut contributions = Self::return_outdated_regions(order_id, &order.requirements);
+			ensure!(
+				contributions
+					.iter()
+					.all(|(contributed, _)| contributed.core != region_id.core ||
+						(contributed.mask & region_id.mask).is_void()),
+				Error::<T>::RegionsOverlap
+			);
+			contributions
+				.try_push((region_id, who.clone()))
+				.map_err(|_| Error::<T>::TooManyRegions)?;
+
+			T::Regions::lock(&region_id.into(), Some(who.clone()))?;
+			Self::deposit_event(Event::RegionContributed { order_id, region_id, contributor: who });
+
+			let occupied_bits = contributions.iter().fold(0u32, |total, (region_id, _)| {
+				total.saturating_add(region_id.mask.count_ones())
+			});
+			if core_occupancy(occupied_bits) < order.requirements.core_occupancy.into() {
+				RegionContributions::<T>::insert(order_id, contributions);
 				return Ok(())
 			}
 
-			// We will burn the region since it has been assigned with `Final` finality.
-			T::Regions::burn(&region_id.into(), None)?;
+			RegionContributions::<T>::remove(order_id);
+			Self::do_fulfill_order_with_regions(order_id, order, contributions)
+		}
+
+		/// Withdraw a region contributed towards fulfilling an order.
+		///
+		/// The region gets unlocked. Regions can be withdrawn even if the order got fulfilled
+		/// with other regions or removed in the meantime.
+		///
+		/// ## Arguments:
+		/// - `origin`: Signed origin; the account that contributed the region.
+		/// - `order_id`: The order to which the region was contributed.
+		/// - `region_id`: The region to withdraw.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::withdraw_region())]
+		pub fn withdraw_region(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			region_id: RegionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			RegionContributions::<T>::try_mutate_exists(order_id, |maybe_contributions| {
+				let contributions =
+					maybe_contributions.as_mut().ok_or(Error::<T>::UnknownContribution)?;
+				let index = contributions
+					.iter()
+					.position(|(contributed, contributor)| {
+						*contributed == region_id && *contributor == who
+					})
+					.ok_or(Error::<T>::UnknownContribution)?;
+				contributions.remove(index);
+
+				if contributions.is_empty() {
+					*maybe_contributions = None;
+				}
+				Ok::<(), DispatchError>(())
+			})?;
+
+			T::Regions::unlock(&region_id.into(), Some(who.clone()))?;
+			Self::deposit_event(Event::RegionWithdrawn { order_id, region_id, contributor: who });
 
-			Self::deposit_event(Event::RegionAssigned { region_id, para_id: order.para_id });
 			Ok(())
 		}
 
@@ -261,15 +400,192 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Hands over the region to the order creator, rewards the seller and tries to assign the
+		/// region to the ordering parachain.
+		pub(crate) fn do_fulfill_order(
+			who: T::AccountId,
+			order_id: OrderId,
+			order: OrderOf<T>,
+			region_id: RegionId,
+			reward: BalanceOf<T>,
+		) -> DispatchResult {
+			// Transfer the region to the order creator
+			//
+			// We will try to assign the region to the task, however before we do that we will
+			// transfer it to the order creator. This way in case the assignment fails the region
+			// will still be owned by the creator.
+			T::Regions::transfer(&region_id.into(), &order.creator)?;
+			T::Regions::lock(&region_id.into(), None)?;
+
+			let order_account = T::OrderToAccountId::convert(order_id);
+			<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
+				&order_account,
+				&who,
+				reward,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			T::Orders::pay_asset_rewards(&order_id, &[(who.clone(), 1)]);
+
+			// Remove the order, or renew it in case it is a recurring one.
+			T::Orders::on_fulfilled(&order_id);
+
+			// Regions contributed to the order are returned, unless they cover the renewed order.
+			let contributions = match T::Orders::order(&order_id) {
+				Some(renewed) => Self::return_outdated_regions(order_id, &renewed.requirements),
+				None => {
+					for (region_id, contributor) in RegionContributions::<T>::get(order_id) {
+						Self::return_region(order_id, region_id, &contributor);
+					}
+					Default::default()
+				},
+			};
+			if contributions.is_empty() {
+				RegionContributions::<T>::remove(order_id);
+			} else {
+				RegionContributions::<T>::insert(order_id, contributions);
+			}
+
+			Self::deposit_event(Event::OrderProcessed { order_id, region_id, seller: who, reward });
+
+			Self::try_assign(region_id, order.para_id)
+		}
+
+		/// Fulfills an order with several regions, sharing the reward among the contributors pro
+		/// rata to the occupancy of their regions.
+		pub(crate) fn do_fulfill_order_with_regions(
+			order_id: OrderId,
+			order: OrderOf<T>,
+			contributions: BoundedVec<(RegionId, T::AccountId), T::MaxOrderRegions>,
+		) -> DispatchResult {
+			let order_account = T::OrderToAccountId::convert(order_id);
+			let reward = T::Orders::reward(&order_id, T::Currency::free_balance(&order_account));
+
+			let total_occupancy = contributions.iter().fold(0u32, |total, (region_id, _)| {
+				total.saturating_add(region_id.mask.count_ones())
+			});
+
+			// The last contributor gets the remainder so that no dust of the reward is left over.
+			let mut remaining = reward;
+			for (index, (region_id, contributor)) in contributions.iter().enumerate() {
+				let share = if index == contributions.len() - 1 {
+					remaining
+				} else {
+					Perbill::from_rational(region_id.mask.count_ones(), total_occupancy)
+						.mul_floor(reward)
+				};
+				remaining = remaining.saturating_sub(share);
+
+				<<T as Config>::Currency as Currency<T::AccountId>>::transfer(
+					&order_account,
+					contributor,
+					share,
+					ExistenceRequirement::AllowDeath,
+				)?;
+
+				// The region was locked when it got contributed.
+				T::Regions::unlock(&(*region_id).into(), None)?;
+				T::Regions::transfer(&(*region_id).into(), &order.creator)?;
+				T::Regions::lock(&(*region_id).into(), None)?;
+
+				Self::deposit_event(Event::OrderProcessed {
+					order_id,
+					region_id: *region_id,
+					seller: contributor.clone(),
+					reward: share,
+				});
+			}
+
+			// Contributions in other assets are shared the same way as the reward.
+			let recipients: Vec<_> = contributions
+				.iter()
+				.map(|(region_id, contributor)| (contributor.clone(), region_id.mask.count_ones()))
+				.collect();
+			T::Orders::pay_asset_rewards(&order_id, &recipients);
+
+			T::Orders::on_fulfilled(&order_id);
+
+			for (region_id, _) in contributions {
+				Self::try_assign(region_id, order.para_id)?;
+			}
+
+			Ok(())
+		}
+
+		/// Returns the regions contributed to the order which no longer cover the timeslices
+		/// required by it, e.g. because the order got updated or renewed in the meantime.
+		///
+		/// Returns the contributions that still match without updating the storage.
+		pub(crate) fn return_outdated_regions(
+			order_id: OrderId,
+			requirements: &Requirements,
+		) -> BoundedVec<(RegionId, T::AccountId), T::MaxOrderRegions> {
+			let mut contributions = RegionContributions::<T>::get(order_id);
+			contributions.retain(|(region_id, contributor)| {
+				let matching = T::Regions::region(&(*region_id).into())
+					.and_then(|region| region.record.get())
+					.map_or(false, |record| {
+						Self::ensure_matching_period(*region_id, &record, requirements).is_ok()
+					});
+				if !matching {
+					Self::return_region(order_id, *region_id, contributor);
+				}
+				matching
+			});
+			contributions
+		}
+
+		/// Unlocks a region contributed to an order, handing it back to the contributor.
+		pub(crate) fn return_region(
+			order_id: OrderId,
+			region_id: RegionId,
+			contributor: &T::AccountId,
+		) {
+			// Fails only if the region no longer exists, in which case there is nothing to return.
+			if let Err(err) = T::Regions::unlock(&region_id.into(), Some(contributor.clone())) {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to unlock region {:?} contributed to order {:?}: {:?}",
+					region_id,
+					order_id,
+					err
+				);
+			}
+			Self::deposit_event(Event::RegionWithdrawn {
+				order_id,
+				region_id,
+				contributor: contributor.clone(),
+			});
+		}
+
+		/// Tries to assign the region to `para_id`.
+		///
+		/// If the assignment fails, we don't return an error; instead, we return ok and allow
+		/// anyone to attempt to assign the region.
+		pub(crate) fn try_assign(region_id: RegionId, para_id: ParaId) -> DispatchResult {
+			if let Err(err) = T::RegionAssigner::assign(region_id, para_id) {
+				// Even though the region will be owned by the creator, anyone can assign it to the
+				// task by calling the `assign` extrinsic.
+				RegionAssignments::<T>::insert(region_id, para_id);
+
+				Self::deposit_event(Event::AssignmentFailed(err));
+				return Ok(())
+			}
+
+			// We will burn the region since it has been assigned with `Final` finality.
+			T::Regions::burn(&region_id.into(), None)?;
+
+			Self::deposit_event(Event::RegionAssigned { region_id, para_id });
+			Ok(())
+		}
+
 		pub(crate) fn ensure_matching_requirements(
 			region_id: RegionId,
 			record: RegionRecordOf<T>,
 			requirements: Requirements,
 		) -> DispatchResult {
-			ensure!(region_id.begin <= requirements.begin, Error::<T>::RegionStartsTooLate);
-			ensure!(record.end >= requirements.end, Error::<T>::RegionEndsTooSoon);
+			Self::ensure_matching_period(region_id, &record, &requirements)?;
 
-			let mask_as_nominator = region_id.mask.count_ones() * (57600 / 80);
+			let mask_as_nominator = core_occupancy(region_id.mask.count_ones());
 			ensure!(
 				mask_as_nominator >= requirements.core_occupancy.into(),
 				Error::<T>::RegionCoreOccupancyInsufficient
@@ -277,5 +593,31 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Ensures the region covers the timeslices required by the order.
+		pub(crate) fn ensure_matching_period(
+			region_id: RegionId,
+			record: &RegionRecordOf<T>,
+			requirements: &Requirements,
+		) -> DispatchResult {
+			ensure!(region_id.begin <= requirements.begin, Error::<T>::RegionStartsTooLate);
+			ensure!(record.end >= requirements.end, Error::<T>::RegionEndsTooSoon);
+
+			Ok(())
+		}
+
+		/// Returns whether the region satisfies the requirements of the order.
+		///
+		/// Used by fulfillers for finding the orders they can fulfill with their regions.
+		pub fn region_matches_order(region_id: RegionId, order_id: OrderId) -> bool {
+			let Some(order) = T::Orders::order(&order_id) else { return false };
+			let Some(record) =
+				T::Regions::region(&region_id.into()).and_then(|region| region.record.get())
+			else {
+				return false
+			};
+
+			Self::ensure_matching_requirements(region_id, record, order.requirements).is_ok()
+		}
 	}
 }
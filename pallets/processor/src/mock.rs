@@ -17,12 +17,13 @@ use core::cell::RefCell;
 use frame_support::{
 	pallet_prelude::*,
 	parameter_types,
-	traits::{fungible::Mutate, tokens::Preservation, Everything},
+	traits::{fungible::Mutate, nonfungible::Transfer, tokens::Preservation, Everything},
 	weights::{
 		constants::ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients,
 		WeightToFeePolynomial,
 	},
 };
+use frame_system::{EnsureNever, EnsureRoot};
 use ismp::{
 	consensus::StateMachineId,
 	dispatcher::{DispatchRequest, FeeMetadata, IsmpDispatcher},
@@ -32,9 +33,11 @@ use ismp::{
 };
 use ismp_testsuite::mocks::Host;
 use order_primitives::{OrderId, ParaId};
+use orml_traits::parameter_type_with_key;
 use pallet_broker::RegionId;
 use pallet_orders::FeeHandler;
 use pallet_regions::primitives::StateMachineHeightProvider;
+use region_primitives::RegionMarket;
 use smallvec::smallvec;
 use sp_core::{ConstU64, H256};
 use sp_runtime::{
@@ -73,6 +76,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Balances: pallet_balances,
+		Tokens: orml_tokens,
 		Orders: pallet_orders::{Pallet, Call, Storage, Event<T>},
 		Regions: pallet_regions::{Pallet, Call, Storage, Event<T>},
 		Processor: crate::{Pallet, Call, Storage, Event<T>},
@@ -192,6 +196,26 @@ impl pallet_regions::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		1
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = ();
+}
+
 pub struct OrderCreationFeeHandler;
 impl FeeHandler<AccountId, u64> for OrderCreationFeeHandler {
 	fn handle(who: &AccountId, fee: u64) -> DispatchResult {
@@ -217,10 +241,21 @@ impl pallet_orders::Config for Test {
 	type Currency = Balances;
 	type OrderCreationCost = ConstU64<100>;
 	type MinimumContribution = ConstU64<50>;
+	type BulkPeriod = ConstU32<5040>;
 	type RCBlockNumberProvider = RelayBlockNumberProvider;
 	type OrderToAccountId = OrderToAccountId;
 	type TimeslicePeriod = ConstU64<80>;
 	type OrderCreationFeeHandler = OrderCreationFeeHandler;
+	type CancellationPenaltyHandler = OrderCreationFeeHandler;
+	type CancellationPenaltyOrigin = EnsureRoot<AccountId>;
+	type AssetId = u32;
+	type Assets = Tokens;
+	type SupportedAssets = Everything;
+	type MaxOrderAssets = ConstU32<4>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAssetId = ConstU32<1>;
+	type XcmOrigin = EnsureNever<MultiLocation>;
+	type LocationToAccountId = ();
 	type WeightInfo = ();
 }
 
@@ -268,6 +303,50 @@ impl crate::RegionAssigner for DummyRegionAssigner {
 	}
 }
 
+thread_local! {
+	pub static LISTINGS: RefCell<Vec<(RegionId, AccountId, u64)>> = Default::default();
+}
+
+/// Puts a region on sale in the mock market for the given total price.
+pub fn list_region(seller: AccountId, region_id: RegionId, price: u64) {
+	LISTINGS.with(|listings| {
+		let mut listings = listings.borrow_mut();
+		listings.retain(|(id, _, _)| *id != region_id);
+		listings.push((region_id, seller, price));
+	});
+}
+
+pub struct MockMarket;
+impl RegionMarket<AccountId, u64> for MockMarket {
+	fn purchase(
+		buyer: &AccountId,
+		region_id: RegionId,
+		max_price: u64,
+	) -> Result<u64, DispatchError> {
+		let (seller, price) = LISTINGS
+			.with(|listings| {
+				listings
+					.borrow()
+					.iter()
+					.find(|(id, _, _)| *id == region_id)
+					.map(|(_, seller, price)| (*seller, *price))
+			})
+			.ok_or(DispatchError::Other("NotListed"))?;
+		ensure!(price <= max_price, DispatchError::Other("PriceTooHigh"));
+
+		LISTINGS.with(|listings| listings.borrow_mut().retain(|(id, _, _)| *id != region_id));
+		<Balances as Mutate<AccountId>>::transfer(buyer, &seller, price, Preservation::Expendable)?;
+		<Regions as Transfer<AccountId>>::transfer(&region_id.into(), buyer)?;
+		Ok(price)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn list(seller: &AccountId, region_id: RegionId, timeslice_price: u64) -> DispatchResult {
+		list_region(*seller, region_id, timeslice_price);
+		Ok(())
+	}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
@@ -275,6 +354,8 @@ impl crate::Config for Test {
 	type Orders = Orders;
 	type OrderToAccountId = OrderToAccountId;
 	type Regions = Regions;
+	type Market = MockMarket;
+	type MaxOrderRegions = ConstU32<4>;
 	type AssignmentCallEncoder = AssignmentCallEncoder;
 	type RegionAssigner = DummyRegionAssigner;
 	type CoretimeChain = CoretimeChain;
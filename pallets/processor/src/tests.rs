@@ -15,18 +15,20 @@
 
 use crate::{
 	mock::{
-		assignments, new_test_ext, Balances, Orders, Processor, Regions, RuntimeOrigin, System,
-		Test,
+		assignments, list_region, new_test_ext, Balances, OrderToAccountId, Orders, Processor,
+		Regions, RuntimeOrigin, System, Test, Tokens,
 	},
 	Error, Event,
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{nonfungible::Mutate, Currency},
+	traits::{nonfungible::Mutate, Currency, Get},
 };
 use nonfungible_primitives::LockableNonFungible;
 use order_primitives::{Order, ParaId, Requirements};
+use orml_traits::MultiCurrency;
 use pallet_broker::{CoreMask, RegionId, RegionRecord};
+use sp_runtime::{traits::Convert, DispatchError};
 
 #[test]
 fn fulfill_order_works() {
@@ -44,11 +46,18 @@ fn fulfill_order_works() {
 		assert_ok!(Orders::create_order(
 			RuntimeOrigin::signed(order_creator.clone()),
 			2000.into(),
-			requirements.clone()
+			requirements.clone(),
+			None
 		));
 		assert_eq!(
 			Orders::orders(0),
-			Some(Order { para_id: 2000.into(), creator: order_creator, requirements })
+			Some(Order {
+				para_id: 2000.into(),
+				creator: order_creator,
+				requirements,
+				max_reward: None,
+				funding_target: None
+			})
 		);
 
 		// 2. make contributions to an order
@@ -108,6 +117,389 @@ fn fulfill_order_works() {
 	});
 }
 
+#[test]
+fn fulfill_recurring_order_works() {
+	new_test_ext(vec![(2000, 1000), (10, 1000), (11, 1000)]).execute_with(|| {
+		let region_owner = 1;
+		let order_creator = 2000;
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+
+		assert_ok!(Orders::create_recurring_order(
+			RuntimeOrigin::signed(order_creator),
+			2000.into(),
+			requirements,
+			600
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 800));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(11), 0, 700));
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &region_owner));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Only the reward gets paid out, the order is renewed for the next bulk period:
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(region_owner), 0, region_id));
+		System::assert_has_event(
+			Event::OrderProcessed { order_id: 0, region_id, seller: region_owner, reward: 600 }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(region_owner), 600);
+
+		let period = <Test as pallet_orders::Config>::BulkPeriod::get();
+		let renewed = Requirements { begin: period, end: period + 8, core_occupancy: 28800 };
+		assert_eq!(
+			Orders::orders(0),
+			Some(Order {
+				para_id: 2000.into(),
+				creator: order_creator,
+				requirements: renewed,
+				max_reward: Some(600),
+				funding_target: None
+			})
+		);
+
+		// The region of the previous period no longer matches:
+		let region_id = RegionId { begin: 0, core: 1, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &region_owner));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert_noop!(
+			Processor::fulfill_order(RuntimeOrigin::signed(region_owner), 0, region_id),
+			Error::<Test>::RegionEndsTooSoon
+		);
+
+		// The order stops once the remaining funds no longer cover the reward:
+		let region_id = RegionId { begin: period, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &region_owner));
+		assert_ok!(Regions::set_record(
+			region_id,
+			RegionRecord { end: period + 8, owner: 1, paid: None }
+		));
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(region_owner), 0, region_id));
+		assert_eq!(Balances::free_balance(region_owner), 1200);
+		assert!(Orders::orders(0).is_none());
+		assert_eq!(Balances::free_balance(OrderToAccountId::convert(0)), 300);
+	});
+}
+
+#[test]
+fn excess_contributions_are_refunded() {
+	new_test_ext(vec![(2000, 1000), (10, 1000), (11, 1000)]).execute_with(|| {
+		let region_owner = 1;
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 28800 };
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2000),
+			2000.into(),
+			requirements,
+			Some(1000)
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 900));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(11), 0, 600));
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &region_owner));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Only the agreed reward is paid out:
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(region_owner), 0, region_id));
+		System::assert_has_event(
+			Event::OrderProcessed { order_id: 0, region_id, seller: region_owner, reward: 1000 }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(region_owner), 1000);
+		assert!(Orders::orders(0).is_none());
+
+		// The rest is claimable by the contributors pro rata:
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(10), 0));
+		assert_ok!(Orders::remove_contribution(RuntimeOrigin::signed(11), 0));
+		assert_eq!(Balances::free_balance(10), 400);
+		assert_eq!(Balances::free_balance(11), 600);
+		assert_eq!(Balances::free_balance(OrderToAccountId::convert(0)), 0);
+	});
+}
+
+#[test]
+fn fulfill_order_pays_asset_contributions() {
+	new_test_ext(vec![(2000, 1000), (10, 1000)]).execute_with(|| {
+		let region_owner = 1;
+		let asset = 1;
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2000),
+			2000.into(),
+			Requirements { begin: 0, end: 8, core_occupancy: 28800 },
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 500));
+		assert_ok!(Tokens::deposit(asset, &10, 1000));
+		assert_ok!(Orders::contribute_asset(RuntimeOrigin::signed(10), 0, asset, 300));
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &region_owner));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(region_owner), 0, region_id));
+
+		// The fulfiller receives the contributions in all of the assets:
+		assert_eq!(Balances::free_balance(region_owner), 500);
+		assert_eq!(Tokens::free_balance(asset, &region_owner), 300);
+		assert_eq!(Tokens::free_balance(asset, &OrderToAccountId::convert(0)), 0);
+	});
+}
+
+#[test]
+fn purchase_and_fulfill_order_works() {
+	new_test_ext(vec![(2000, 1000), (10, 1000), (11, 1000)]).execute_with(|| {
+		let seller = 1;
+		let caller = 3;
+		let order_creator = 2000;
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+
+		// Create and fund an order with a reward of 1300.
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(order_creator),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 500));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(11), 0, 800));
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&region_id.into(), &seller));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+
+		// Fails if the region is not listed:
+		assert_noop!(
+			Processor::purchase_and_fulfill_order(RuntimeOrigin::signed(caller), 0, region_id),
+			DispatchError::Other("NotListed")
+		);
+
+		// Fails atomically if the reward doesn't cover the price:
+		list_region(seller, region_id, 1500);
+		assert_noop!(
+			Processor::purchase_and_fulfill_order(RuntimeOrigin::signed(caller), 0, region_id),
+			DispatchError::Other("PriceTooHigh")
+		);
+
+		// Works when the reward covers the price:
+		list_region(seller, region_id, 1000);
+		assert_ok!(Processor::purchase_and_fulfill_order(
+			RuntimeOrigin::signed(caller),
+			0,
+			region_id
+		));
+
+		System::assert_has_event(
+			Event::OrderProcessed { order_id: 0, region_id, seller: caller, reward: 300 }.into(),
+		);
+		System::assert_has_event(Event::RegionAssigned { region_id, para_id: 2000.into() }.into());
+
+		// The seller receives the price and the caller the rest of the reward:
+		assert_eq!(Balances::free_balance(seller), 1000);
+		assert_eq!(Balances::free_balance(caller), 300);
+
+		assert!(Orders::orders(0).is_none());
+		assert!(Regions::regions(region_id).is_none());
+		assert_eq!(assignments(), vec![(region_id, 2000.into())]);
+	});
+}
+
+#[test]
+fn fulfill_order_with_multiple_regions_works() {
+	new_test_ext(vec![(2000, 1000), (10, 1000), (11, 1000), (12, 1000)]).execute_with(|| {
+		let order_creator = 2000;
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 57600, // Full core.
+		};
+
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(order_creator),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 500));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(11), 0, 800));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(12), 0, 200));
+
+		// Half of core 0, a quarter of core 0 and a quarter of core 1:
+		let half = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(0, 40) };
+		let quarter = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(40, 60) };
+		let other_core = RegionId { begin: 0, core: 1, mask: CoreMask::from_chunk(0, 20) };
+		let overlapping = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(30, 50) };
+		let too_short = RegionId { begin: 0, core: 2, mask: CoreMask::complete() };
+		for (region_id, owner, end) in [
+			(half, 1, 8),
+			(quarter, 2, 8),
+			(other_core, 3, 8),
+			(overlapping, 2, 8),
+			(too_short, 3, 7),
+		] {
+			assert_ok!(Regions::mint_into(&region_id.into(), &owner));
+			assert_ok!(Regions::set_record(region_id, RegionRecord { end, owner, paid: None }));
+		}
+
+		// Failure: the region must cover the required timeslices.
+		assert_noop!(
+			Processor::contribute_region(RuntimeOrigin::signed(3), 0, too_short),
+			Error::<Test>::RegionEndsTooSoon
+		);
+		// Failure: only the owner can contribute the region.
+		assert_noop!(
+			Processor::contribute_region(RuntimeOrigin::signed(2), 0, half),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(1), 0, half));
+		System::assert_last_event(
+			Event::RegionContributed { order_id: 0, region_id: half, contributor: 1 }.into(),
+		);
+		assert!(Regions::regions(half).unwrap().locked);
+
+		// Failure: the masks of the regions on the same core must not overlap.
+		assert_noop!(
+			Processor::contribute_region(RuntimeOrigin::signed(2), 0, overlapping),
+			Error::<Test>::RegionsOverlap
+		);
+
+		// Contributed regions can be withdrawn by the contributor:
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(2), 0, quarter));
+		assert_noop!(
+			Processor::withdraw_region(RuntimeOrigin::signed(1), 0, quarter),
+			Error::<Test>::UnknownContribution
+		);
+		assert_ok!(Processor::withdraw_region(RuntimeOrigin::signed(2), 0, quarter));
+		System::assert_last_event(
+			Event::RegionWithdrawn { order_id: 0, region_id: quarter, contributor: 2 }.into(),
+		);
+		assert!(!Regions::regions(quarter).unwrap().locked);
+		assert_eq!(Processor::region_contributions(0).into_inner(), vec![(half, 1)]);
+
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(2), 0, quarter));
+		assert!(Orders::orders(0).is_some());
+
+		// The order gets fulfilled once the regions reach the required occupancy:
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(3), 0, other_core));
+		assert!(Orders::orders(0).is_none());
+		assert!(Processor::region_contributions(0).is_empty());
+
+		// The reward is shared pro rata to the occupancy of the regions:
+		for (region_id, seller, reward) in [(half, 1, 750), (quarter, 2, 375), (other_core, 3, 375)]
+		{
+			System::assert_has_event(
+				Event::OrderProcessed { order_id: 0, region_id, seller, reward }.into(),
+			);
+			System::assert_has_event(
+				Event::RegionAssigned { region_id, para_id: 2000.into() }.into(),
+			);
+			assert_eq!(Balances::free_balance(seller), reward);
+			assert!(Regions::regions(region_id).is_none());
+		}
+		assert_eq!(
+			assignments(),
+			vec![(half, 2000.into()), (quarter, 2000.into()), (other_core, 2000.into())]
+		);
+	});
+}
+
+#[test]
+fn contributed_regions_are_returned_once_outdated() {
+	new_test_ext(vec![(2000, 1000), (10, 1000)]).execute_with(|| {
+		let order_creator = 2000;
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 57600 };
+
+		assert_ok!(Orders::create_recurring_order(
+			RuntimeOrigin::signed(order_creator),
+			2000.into(),
+			requirements,
+			200
+		));
+		assert_ok!(Orders::contribute(RuntimeOrigin::signed(10), 0, 800));
+
+		let period = <Test as pallet_orders::Config>::BulkPeriod::get();
+		// Covers only the current period:
+		let short = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(0, 20) };
+		// Covers the renewed order as well:
+		let long = RegionId { begin: 0, core: 1, mask: CoreMask::from_chunk(0, 20) };
+		let full = RegionId { begin: 0, core: 2, mask: CoreMask::complete() };
+		for (region_id, owner, end) in [(short, 1, 8), (long, 2, period + 8), (full, 3, 8)] {
+			assert_ok!(Regions::mint_into(&region_id.into(), &owner));
+			assert_ok!(Regions::set_record(region_id, RegionRecord { end, owner, paid: None }));
+		}
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(1), 0, short));
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(2), 0, long));
+		assert!(Orders::orders(0).is_some());
+
+		// The order gets fulfilled with a single region and renewed:
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(3), 0, full));
+
+		// The region which doesn't cover the renewed order gets returned:
+		System::assert_has_event(
+			Event::RegionWithdrawn { order_id: 0, region_id: short, contributor: 1 }.into(),
+		);
+		assert!(!Regions::regions(short).unwrap().locked);
+		assert!(Regions::regions(long).unwrap().locked);
+		assert_eq!(Processor::region_contributions(0).into_inner(), vec![(long, 2)]);
+
+		// After the next renewal the other region gets returned as well:
+		let next = RegionId { begin: period, core: 0, mask: CoreMask::complete() };
+		assert_ok!(Regions::mint_into(&next.into(), &3));
+		assert_ok!(Regions::set_record(
+			next,
+			RegionRecord { end: period + 8, owner: 3, paid: None }
+		));
+		assert_ok!(Processor::fulfill_order(RuntimeOrigin::signed(3), 0, next));
+		assert!(Orders::orders(0).is_some());
+		System::assert_has_event(
+			Event::RegionWithdrawn { order_id: 0, region_id: long, contributor: 2 }.into(),
+		);
+		assert!(!Regions::regions(long).unwrap().locked);
+		assert!(Processor::region_contributions(0).is_empty());
+	});
+}
+
+#[test]
+fn contributed_regions_are_rechecked_before_fulfilling() {
+	new_test_ext(vec![(2000, 1000)]).execute_with(|| {
+		let order_creator = 2000;
+		let requirements = Requirements { begin: 0, end: 8, core_occupancy: 57600 };
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(order_creator),
+			2000.into(),
+			requirements,
+			None
+		));
+
+		let first = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(0, 40) };
+		let second = RegionId { begin: 0, core: 0, mask: CoreMask::from_chunk(40, 80) };
+		for (region_id, owner, end) in [(first, 1, 8), (second, 2, 10)] {
+			assert_ok!(Regions::mint_into(&region_id.into(), &owner));
+			assert_ok!(Regions::set_record(region_id, RegionRecord { end, owner, paid: None }));
+		}
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(1), 0, first));
+
+		// The order changes, so that the first region no longer covers it:
+		let requirements = Requirements { begin: 0, end: 10, core_occupancy: 57600 };
+		assert_ok!(Orders::update_order(RuntimeOrigin::signed(order_creator), 0, requirements));
+
+		// The first region gets returned instead of fulfilling the order:
+		assert_ok!(Processor::contribute_region(RuntimeOrigin::signed(2), 0, second));
+		System::assert_has_event(
+			Event::RegionWithdrawn { order_id: 0, region_id: first, contributor: 1 }.into(),
+		);
+		assert!(!Regions::regions(first).unwrap().locked);
+		assert!(Orders::orders(0).is_some());
+		assert_eq!(Processor::region_contributions(0).into_inner(), vec![(second, 2)]);
+	});
+}
+
 #[test]
 fn assign_works() {
 	new_test_ext(vec![]).execute_with(|| {
@@ -175,3 +567,36 @@ fn ensure_matching_requirements_works() {
 		),);
 	})
 }
+
+#[test]
+fn region_matches_order_works() {
+	new_test_ext(vec![(2000, 1000)]).execute_with(|| {
+		let requirements = Requirements {
+			begin: 0,
+			end: 8,
+			core_occupancy: 28800, // Half of a core.
+		};
+		assert_ok!(Orders::create_order(
+			RuntimeOrigin::signed(2000),
+			2000.into(),
+			requirements.clone(),
+			None
+		));
+
+		let region_id = RegionId { begin: 0, core: 0, mask: CoreMask::complete() };
+		// Unknown region:
+		assert!(!Processor::region_matches_order(region_id, 0));
+
+		assert_ok!(Regions::mint_into(&region_id.into(), &1));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert!(Processor::region_matches_order(region_id, 0));
+		// Unknown order:
+		assert!(!Processor::region_matches_order(region_id, 1));
+
+		// Region core occupancy insufficient:
+		let region_id = RegionId { begin: 0, core: 1, mask: CoreMask::from_chunk(0, 10) };
+		assert_ok!(Regions::mint_into(&region_id.into(), &1));
+		assert_ok!(Regions::set_record(region_id, RegionRecord { end: 8, owner: 1, paid: None }));
+		assert!(!Processor::region_matches_order(region_id, 0));
+	})
+}
@@ -13,37 +13,11 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-//! Autogenerated weights for `pallet_processor`
+//! Estimated weights for `pallet_processor`.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2024-07-26, STEPS: `20`, REPEAT: `50`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `sergej-B650-AORUS-ELITE-AX`, CPU: `AMD Ryzen 9 7900X3D 12-Core Processor`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("cocos")`, DB CACHE: `1024`
-
-// Executed Command:
-// ./target/release/regionx-node
-// benchmark
-// pallet
-// --pallet
-// pallet-processor
-// --extrinsic=*
-// --template
-// ./config/frame-weight-template.hbs
-// --output
-// ./pallets/processor/src/weights.rs
-// --steps
-// 20
-// --repeat
-// 50
-// --header
-// ./config/HEADER-GPL3
-// --chain
-// cocos
-// --wasm-execution
-// compiled
-// --heap-pages
-// 4096
+//! NOTE: These weights were not produced by the benchmark CLI. They are estimates based on the
+//! benchmarks of comparable calls and must be regenerated by running `benchmark pallet` for
+//! `pallet_processor` before they are relied upon.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -57,6 +31,9 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn fulfill_order() -> Weight;
 	fn assign() -> Weight;
+	fn purchase_and_fulfill_order() -> Weight;
+	fn contribute_region(n: u32) -> Weight;
+	fn withdraw_region() -> Weight;
 }
 
 /// Weights for `pallet_processor` using the Substrate node and recommended hardware.
@@ -65,21 +42,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `Orders::Orders` (r:1 w:1)
-	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(66), added: 2541, mode: `MaxEncodedLen`)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
 	/// Storage: `Tokens::Accounts` (r:1 w:0)
 	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
 	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Processor::RegionAssignments` (r:0 w:1)
 	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
 	fn fulfill_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `572`
 		//  Estimated: `3584`
 		// Minimum execution time: 43_483_000 picoseconds.
 		Weight::from_parts(45_266_000, 3584)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
 	}
 	/// Storage: `Processor::RegionAssignments` (r:1 w:0)
 	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
@@ -93,6 +78,100 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(22_143_000, 3501)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 	}
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::Orders` (r:1 w:1)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Listings` (r:1 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(1004), added: 3479, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
+	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Processor::RegionAssignments` (r:0 w:1)
+	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn purchase_and_fulfill_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1683`
+		//  Estimated: `11733`
+		// Minimum execution time: 118_204_000 picoseconds.
+		Weight::from_parts(121_557_000, 11733)
+			.saturating_add(T::DbWeight::get().reads(25_u64))
+			.saturating_add(T::DbWeight::get().writes(25_u64))
+	}
+	/// Storage: `Regions::Regions` (r:16 w:16)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::Orders` (r:1 w:1)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Processor::RegionContributions` (r:1 w:1)
+	/// Proof: `Processor::RegionContributions` (`max_values`: None, `max_size`: Some(1046), added: 3521, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:17 w:17)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
+	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Processor::RegionAssignments` (r:0 w:16)
+	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn contribute_region(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `689 + n * (163 ±0)`
+		//  Estimated: `4511 + n * (2594 ±0)`
+		// Minimum execution time: 52_310_000 picoseconds.
+		Weight::from_parts(31_908_000, 4511)
+			// Standard Error: 18_204
+			.saturating_add(Weight::from_parts(29_615_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2594).saturating_mul(n.into()))
+	}
+	/// Storage: `Processor::RegionContributions` (r:1 w:1)
+	/// Proof: `Processor::RegionContributions` (`max_values`: None, `max_size`: Some(1046), added: 3521, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	fn withdraw_region() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1214`
+		//  Estimated: `4511`
+		// Minimum execution time: 21_873_000 picoseconds.
+		Weight::from_parts(22_640_000, 4511)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -100,21 +179,29 @@ impl WeightInfo for () {
 	/// Storage: `Regions::Regions` (r:1 w:1)
 	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
 	/// Storage: `Orders::Orders` (r:1 w:1)
-	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(66), added: 2541, mode: `MaxEncodedLen`)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
 	/// Storage: `Tokens::Accounts` (r:1 w:0)
 	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
 	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
 	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Processor::RegionAssignments` (r:0 w:1)
 	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
 	fn fulfill_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `572`
 		//  Estimated: `3584`
 		// Minimum execution time: 43_483_000 picoseconds.
 		Weight::from_parts(45_266_000, 3584)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
 	}
 	/// Storage: `Processor::RegionAssignments` (r:1 w:0)
 	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
@@ -128,4 +215,98 @@ impl WeightInfo for () {
 		Weight::from_parts(22_143_000, 3501)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 	}
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::Orders` (r:1 w:1)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Market::Listings` (r:1 w:1)
+	/// Proof: `Market::Listings` (`max_values`: None, `max_size`: Some(1004), added: 3479, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
+	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainSystem::LastRelayChainBlockNumber` (r:1 w:0)
+	/// Proof: `ParachainSystem::LastRelayChainBlockNumber` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::MarketFee` (r:1 w:0)
+	/// Proof: `Market::MarketFee` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PriceHistory` (r:1 w:1)
+	/// Proof: `Market::PriceHistory` (`max_values`: Some(1), `max_size`: Some(1801), added: 2296, mode: `MaxEncodedLen`)
+	/// Storage: `Market::PeriodPrices` (r:1 w:1)
+	/// Proof: `Market::PeriodPrices` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Market::SellerListings` (r:1 w:1)
+	/// Proof: `Market::SellerListings` (`max_values`: None, `max_size`: Some(1643), added: 4118, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
+	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Market::ListingIndex` (r:0 w:1)
+	/// Proof: `Market::ListingIndex` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Processor::RegionAssignments` (r:0 w:1)
+	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn purchase_and_fulfill_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1683`
+		//  Estimated: `11733`
+		// Minimum execution time: 118_204_000 picoseconds.
+		Weight::from_parts(121_557_000, 11733)
+			.saturating_add(RocksDbWeight::get().reads(25_u64))
+			.saturating_add(RocksDbWeight::get().writes(25_u64))
+	}
+	/// Storage: `Regions::Regions` (r:16 w:16)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::Orders` (r:1 w:1)
+	/// Proof: `Orders::Orders` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Processor::RegionContributions` (r:1 w:1)
+	/// Proof: `Processor::RegionContributions` (`max_values`: None, `max_size`: Some(1046), added: 3521, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:17 w:17)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `ParachainSystem::RelevantMessagingState` (r:1 w:0)
+	/// Proof: `ParachainSystem::RelevantMessagingState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Processor::RegionAssignments` (r:0 w:16)
+	/// Proof: `Processor::RegionAssignments` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::TotalAssetContributions` (r:1 w:0)
+	/// Proof: `Orders::TotalAssetContributions` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::ParaOrders` (r:0 w:1)
+	/// Proof: `Orders::ParaOrders` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Orders::CreatorOrders` (r:0 w:1)
+	/// Proof: `Orders::CreatorOrders` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn contribute_region(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `689 + n * (163 ±0)`
+		//  Estimated: `4511 + n * (2594 ±0)`
+		// Minimum execution time: 52_310_000 picoseconds.
+		Weight::from_parts(31_908_000, 4511)
+			// Standard Error: 18_204
+			.saturating_add(Weight::from_parts(29_615_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2594).saturating_mul(n.into()))
+	}
+	/// Storage: `Processor::RegionContributions` (r:1 w:1)
+	/// Proof: `Processor::RegionContributions` (`max_values`: None, `max_size`: Some(1046), added: 3521, mode: `MaxEncodedLen`)
+	/// Storage: `Regions::Regions` (r:1 w:1)
+	/// Proof: `Regions::Regions` (`max_values`: None, `max_size`: Some(119), added: 2594, mode: `MaxEncodedLen`)
+	fn withdraw_region() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1214`
+		//  Estimated: `4511`
+		// Minimum execution time: 21_873_000 picoseconds.
+		Weight::from_parts(22_640_000, 4511)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
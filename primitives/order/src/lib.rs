@@ -24,7 +24,7 @@ pub type OrderId = u32;
 
 /// The information we store about a Coretime order.
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
-pub struct Order<AccountId> {
+pub struct Order<AccountId, Balance> {
 	/// The `AccountId` that created the order.
 	///
 	/// In most cases this will probably be the sovereign account of the parachain.
@@ -33,6 +33,21 @@ pub struct Order<AccountId> {
 	pub para_id: ParaId,
 	/// Region requirements of the order.
 	pub requirements: Requirements,
+	/// The maximum reward paid out for fulfilling the order.
+	///
+	/// Contributions exceeding the reward remain claimable by the contributors. If `None` all
+	/// of the order funds are paid out.
+	pub max_reward: Option<Balance>,
+	/// The amount of contributions the order aims to raise.
+	///
+	/// Reaching it doesn't affect the order other than signaling that it is fully funded.
+	pub funding_target: Option<Balance>,
+}
+
+/// Converts the number of occupied core mask bits to the fraction of a core used in the order
+/// requirements.
+pub fn core_occupancy(mask_bits: u32) -> u32 {
+	mask_bits.saturating_mul(57600 / 80)
 }
 
 /// The region requirements of an order.
@@ -46,16 +61,35 @@ pub struct Requirements {
 	pub core_occupancy: PartsOf57600,
 }
 
-pub trait OrderInspect<AccountId: Clone> {
+pub trait OrderInspect<AccountId: Clone, Balance> {
 	/// Get the order with the associated id.
 	///
 	/// If `None` the order was not found.
-	fn order(order_id: &OrderId) -> Option<Order<AccountId>>;
+	fn order(order_id: &OrderId) -> Option<Order<AccountId, Balance>>;
 
 	/// Remove an order with the associated id.
 	fn remove_order(order_id: &OrderId);
 }
 
+/// Trait for settling the reward of fulfilled orders.
+pub trait OrderFulfillment<AccountId, Balance> {
+	/// Returns the reward for fulfilling the order out of the funds `available` to the order.
+	fn reward(order_id: &OrderId, available: Balance) -> Balance;
+
+	/// Pays out the contributions made to the order in other assets to the `recipients`,
+	/// sharing them according to the weight of each recipient.
+	///
+	/// Payouts that fail, e.g. due to the existential deposit, remain claimable by the
+	/// contributors.
+	fn pay_asset_rewards(order_id: &OrderId, recipients: &[(AccountId, u32)]);
+
+	/// Handles an order that got fulfilled and its reward paid out.
+	///
+	/// One-shot orders get removed, while recurring orders get renewed for the next period as
+	/// long as they are funded.
+	fn on_fulfilled(order_id: &OrderId);
+}
+
 /// Trait for creating orders. Mostly used for benchmarking.
 pub trait OrderFactory<AccountId> {
 	fn create_order(
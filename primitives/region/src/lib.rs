@@ -15,7 +15,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{pallet_prelude::DispatchResult, Parameter};
+use frame_support::{
+	pallet_prelude::{DispatchError, DispatchResult},
+	Parameter,
+};
 use pallet_broker::{RegionId, RegionRecord};
 use scale_info::TypeInfo;
 use sp_core::H256;
@@ -91,3 +94,33 @@ pub trait RegionFactory<AccountId, RegionRecord> {
 	fn create_region(region_id: RegionId, record: RegionRecord, owner: AccountId)
 		-> DispatchResult;
 }
+
+/// Trait for getting the market price of coretime.
+///
+/// Prices are denominated in the native currency and normalised to the price of a single
+/// timeslice of a full core.
+pub trait CoretimePriceOracle<Balance> {
+	/// The price of the most recent coretime purchase.
+	fn latest_price() -> Option<Balance>;
+
+	/// The time-weighted average price in the given bulk period.
+	///
+	/// For the ongoing bulk period this is the average up until now.
+	fn average_price(period: u32) -> Option<Balance>;
+}
+
+/// Trait for buying regions that are listed on sale.
+pub trait RegionMarket<AccountId, Balance> {
+	/// Purchase the listed region on behalf of `buyer`, paying at most `max_price`.
+	///
+	/// Returns the price that was paid for the region.
+	fn purchase(
+		buyer: &AccountId,
+		region_id: RegionId,
+		max_price: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// List a region on sale. Used for benchmarking.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn list(seller: &AccountId, region_id: RegionId, timeslice_price: Balance) -> DispatchResult;
+}
@@ -15,25 +15,27 @@
 
 use crate::{
 	AccountId, AssetId, AssetRegistry, Authorship, Balance, Balances, PalletCurrency, PotId,
-	RegionXTreasuryAccount, Runtime, RuntimeCall, Tokens, Treasury,
+	RegionXTreasuryAccount, Runtime, RuntimeCall, RuntimeOrigin, Tokens, Treasury,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::{
-	fungibles, tokens::ConversionToAssetBalance, Defensive, ExistenceRequirement, Imbalance,
-	InstanceFilter, OnUnbalanced,
+	fungibles, tokens::ConversionToAssetBalance, Contains, Defensive, EnsureOrigin,
+	ExistenceRequirement, Imbalance, InstanceFilter, OnUnbalanced,
 };
 use order_primitives::ParaId;
 use orml_asset_registry::DefaultAssetMetadata;
-use orml_traits::{asset_registry::AssetProcessor, GetByKey};
+use orml_traits::{asset_registry::AssetProcessor, GetByKey, MultiCurrency};
 use pallet_asset_tx_payment::HandleCredit;
 use pallet_broker::{Finality, RegionId};
 use pallet_processor::assigner::AssignmentCallEncoder as AssignmentCallEncoderT;
+use regionx_runtime_common::assets::{COCOS_ASSET_ID, RELAY_CHAIN_ASSET_ID};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{AccountIdConversion, CheckedDiv},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
 	TokenError,
 };
+use xcm::latest::prelude::*;
 
 #[derive(
 	Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, MaxEncodedLen,
@@ -196,6 +198,72 @@ impl pallet_orders::FeeHandler<AccountId, Balance> for OrderCreationFeeHandler {
 	}
 }
 
+pub struct OrderCancellationPenaltyHandler;
+impl pallet_orders::FeeHandler<AccountId, Balance> for OrderCancellationPenaltyHandler {
+	fn handle(who: &AccountId, penalty: Balance) -> DispatchResult {
+		// We send the penalty for cancelling an order early to the treasury:
+		<Runtime as pallet_orders::Config>::Currency::transfer(
+			who,
+			&RegionXTreasuryAccount::get(),
+			penalty,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Ok(())
+	}
+}
+
+pub struct MarketFeeHandler;
+impl pallet_market::FeeHandler<AccountId, AssetId, Balance> for MarketFeeHandler {
+	fn handle(who: &AccountId, asset: AssetId, fee: Balance) -> DispatchResult {
+		// We send the market fee to the treasury:
+		<Runtime as pallet_market::Config>::Assets::transfer(
+			asset,
+			who,
+			&RegionXTreasuryAccount::get(),
+			fee,
+		)?;
+		Ok(())
+	}
+}
+
+/// Assets in which regions can be listed on the market.
+///
+/// These are the native currency and all the assets registered in the asset registry.
+pub struct MarketAssets;
+impl Contains<AssetId> for MarketAssets {
+	fn contains(asset: &AssetId) -> bool {
+		*asset == COCOS_ASSET_ID || AssetRegistry::metadata(asset).is_some()
+	}
+}
+
+/// Assets orders can be funded with besides the relay chain currency.
+pub struct OrderAssets;
+impl Contains<AssetId> for OrderAssets {
+	fn contains(asset: &AssetId) -> bool {
+		*asset != RELAY_CHAIN_ASSET_ID && MarketAssets::contains(asset)
+	}
+}
+
+/// Ensures the origin is an XCM message sent by a sibling parachain, resolving to its location.
+pub struct EnsureSiblingParachain;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingParachain {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			pallet_xcm::Origin::Xcm(
+				location @ MultiLocation { parents: 1, interior: X1(Parachain(_)) },
+			) => Ok(location),
+			o => Err(o.into()),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(pallet_xcm::Origin::Xcm(MultiLocation::new(1, X1(Parachain(2000)))).into())
+	}
+}
+
 #[derive(Encode, Decode)]
 enum CoretimeRuntimeCalls {
 	#[codec(index = 50)]
@@ -49,11 +49,13 @@ use frame_support::traits::{
 	tokens::{PayFromAccount, UnityAssetBalanceConversion},
 	Currency as PalletCurrency, EqualPrivilegeOnly, LinearStoragePrice, TransformOrigin,
 };
-use order_primitives::OrderId;
+use order_primitives::{Order, OrderId};
+use pallet_broker::Timeslice;
 use pallet_processor::assigner::XcmRegionAssigner;
 use pallet_regions::primitives::StateMachineHeightProvider as StateMachineHeightProviderT;
 use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
 use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
+use region_primitives::CoretimePriceOracle;
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, Get, OpaqueMetadata};
@@ -144,6 +146,21 @@ pub type UncheckedExtrinsic =
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_market::migrations::v1::MigrateToV1<Runtime>,
+	pallet_market::migrations::v2::MigrateToV2<Runtime, ConstU32<RELAY_CHAIN_ASSET_ID>>,
+	pallet_market::migrations::v3::MigrateToV3<Runtime>,
+	pallet_market::migrations::v4::MigrateToV4<Runtime>,
+	pallet_market::migrations::v5::MigrateToV5<Runtime>,
+	pallet_market::migrations::v6::MigrateToV6<Runtime>,
+	pallet_market::migrations::v7::MigrateToV7<Runtime>,
+	pallet_orders::migrations::v1::MigrateToV1<Runtime>,
+	pallet_orders::migrations::v2::MigrateToV2<Runtime>,
+	pallet_orders::migrations::v3::MigrateToV3<Runtime>,
+	pallet_orders::migrations::v4::MigrateToV4<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -151,6 +168,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 /// The relay chain currency on the RegionX parachain.
@@ -194,7 +212,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("regionx-parachain"),
 	impl_name: create_runtime_str!("regionx-parachain"),
 	authoring_version: 1,
-	spec_version: 3_000_000,
+	spec_version: 3_001_000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -369,7 +387,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxHolds = ConstU32<1>;
+	type MaxHolds = ConstU32<2>;
 	/// The type for recording an account's balance.
 	type Balance = Balance;
 	/// The ubiquitous event type.
@@ -756,18 +774,52 @@ impl pallet_treasury::Config for Runtime {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub const MarketPalletId: PalletId = PalletId(*b"rgx/mrkt");
+	pub const ListingDeposit: Balance = COCOS / 10;
+	pub const CleanupReward: Permill = Permill::from_percent(10);
+	pub const MaxListings: u32 = 50;
+	pub const MaxBasketSize: u32 = 10;
+	pub const PriceHistoryLength: u32 = 100;
+	pub const MaxBuyOrders: u32 = 100;
+	pub const MaxAutoPurchases: u32 = 10;
+	pub const MaxSweepCandidates: u32 = 100;
+	// 28 days.
+	pub const BulkPeriod: Timeslice = 5040;
+}
+
 impl pallet_market::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Currency = RelaychainCurrency;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type AssetId = AssetId;
+	type Assets = Currencies;
+	type SupportedAssets = MarketAssets;
+	type NativeAssetId = NativeAssetId;
+	type AssetConverter = AssetRate;
 	type Regions = Regions;
 	type RCBlockNumberProvider = RelaychainDataProvider<Self>;
 	type TimeslicePeriod = ConstU32<80>;
+	type FeeDestination = MarketFeeHandler;
+	type MarketFeeOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureTwoThirdTechnicalCommittee>;
+	type PalletId = MarketPalletId;
+	type ListingDeposit = ListingDeposit;
+	type TreasuryAccount = RegionXTreasuryAccount;
+	type CleanupReward = CleanupReward;
+	type MaxListings = MaxListings;
+	type MaxBasketSize = MaxBasketSize;
+	type PriceHistoryLength = PriceHistoryLength;
+	type MaxBuyOrders = MaxBuyOrders;
+	type MaxAutoPurchases = MaxAutoPurchases;
+	type MaxSweepCandidates = MaxSweepCandidates;
+	type BulkPeriod = BulkPeriod;
 	type WeightInfo = weights::pallet_market::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const OrderCreationCost: Balance = ROC;
 	pub const MinimumContribution: Balance = ROC;
+	pub const MaxOrderAssets: u32 = 4;
 }
 
 pub struct OrderToAccountId;
@@ -782,7 +834,19 @@ impl pallet_orders::Config for Runtime {
 	type Currency = RelaychainCurrency;
 	type OrderCreationCost = OrderCreationCost;
 	type MinimumContribution = MinimumContribution;
+	type BulkPeriod = BulkPeriod;
 	type OrderCreationFeeHandler = OrderCreationFeeHandler;
+	type CancellationPenaltyHandler = OrderCancellationPenaltyHandler;
+	type CancellationPenaltyOrigin =
+		EitherOfDiverse<EnsureRoot<AccountId>, EnsureTwoThirdTechnicalCommittee>;
+	type AssetId = AssetId;
+	type Assets = Currencies;
+	type SupportedAssets = OrderAssets;
+	type MaxOrderAssets = MaxOrderAssets;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAssetId = NativeAssetId;
+	type XcmOrigin = EnsureSiblingParachain;
+	type LocationToAccountId = LocationToAccountId;
 	type OrderToAccountId = OrderToAccountId;
 	type RCBlockNumberProvider = RelaychainDataProvider<Self>;
 	type TimeslicePeriod = ConstU32<80>;
@@ -801,6 +865,8 @@ impl pallet_processor::Config for Runtime {
 	type Orders = Orders;
 	type OrderToAccountId = OrderToAccountId;
 	type Regions = Regions;
+	type Market = pallet_market::PurchaseWith<Runtime, ConstU32<RELAY_CHAIN_ASSET_ID>>;
+	type MaxOrderRegions = ConstU32<16>;
 	type AssignmentCallEncoder = AssignmentCallEncoder;
 	type RegionAssigner = XcmRegionAssigner<Self, LocationToAccountId, OwnParaId, FeeBuffer>;
 	type CoretimeChain = CoretimeChainLocation;
@@ -1126,6 +1192,75 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_market_runtime_api::MarketApi<Block, AccountId, Balance, AssetId> for Runtime {
+		fn quote(
+			region_id: pallet_broker::RegionId,
+			payment_asset: Option<AssetId>,
+		) -> Option<(AssetId, Balance)> {
+			Market::quote(region_id, payment_asset)
+		}
+
+		fn listings(
+			start_after: Option<pallet_broker::RegionId>,
+			limit: u32,
+		) -> (
+			Vec<(pallet_broker::RegionId, pallet_market::Listing<AccountId, Balance, AssetId>)>,
+			Option<pallet_broker::RegionId>,
+		) {
+			Market::active_listings(start_after, limit)
+		}
+
+		fn seller_listings(
+			seller: AccountId,
+		) -> Vec<(pallet_broker::RegionId, pallet_market::Listing<AccountId, Balance, AssetId>)> {
+			Market::listings_of(&seller)
+		}
+
+		fn price_history() -> Vec<pallet_market::PricePoint<Balance>> {
+			Market::price_history().into_inner()
+		}
+
+		fn latest_price() -> Option<Balance> {
+			<Market as CoretimePriceOracle<Balance>>::latest_price()
+		}
+
+		fn average_price(period: u32) -> Option<Balance> {
+			<Market as CoretimePriceOracle<Balance>>::average_price(period)
+		}
+	}
+
+	impl pallet_orders_runtime_api::OrdersApi<Block, AccountId, Balance, AssetId> for Runtime {
+		fn orders(
+			start_after: Option<OrderId>,
+			limit: u32,
+		) -> Vec<(OrderId, Order<AccountId, Balance>)> {
+			Orders::open_orders(start_after, limit)
+		}
+
+		fn para_orders(para_id: ParaId) -> Vec<(OrderId, Order<AccountId, Balance>)> {
+			Orders::orders_of_para(para_id)
+		}
+
+		fn creator_orders(creator: AccountId) -> Vec<(OrderId, Order<AccountId, Balance>)> {
+			Orders::orders_of_creator(&creator)
+		}
+
+		fn total_contributions(order_id: OrderId) -> (Balance, Vec<(AssetId, Balance)>) {
+			(
+				Orders::total_contributions(order_id),
+				Orders::total_asset_contributions(order_id).into_inner(),
+			)
+		}
+
+		fn contribution(order_id: OrderId, who: AccountId) -> Balance {
+			Orders::contributions(order_id, who)
+		}
+
+		fn region_matches(order_id: OrderId, region_id: pallet_broker::RegionId) -> bool {
+			Processor::region_matches_order(region_id, order_id)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {